@@ -58,13 +58,49 @@ pub mod uor_framework {
         ChartError(String),
         /// Errors that arise from concurrency or HPC scheduling.
         ConcurrencyError(String),
+        /// An embedding produced a degenerate result.
+        EmbeddingError(String),
         /// Placeholder for further expansion.
         Other(String),
     }
 
+    impl UorError {
+        /// The same kind of error with `context` prefixed to its message,
+        /// e.g. to say which pipeline stage failed.
+        pub fn with_context(self, context: &str) -> Self {
+            let wrap = |message: String| format!("{}: {}", context, message);
+            match self {
+                UorError::General(m) => UorError::General(wrap(m)),
+                UorError::ChartError(m) => UorError::ChartError(wrap(m)),
+                UorError::ConcurrencyError(m) => UorError::ConcurrencyError(wrap(m)),
+                UorError::EmbeddingError(m) => UorError::EmbeddingError(wrap(m)),
+                UorError::Other(m) => UorError::Other(wrap(m)),
+            }
+        }
+    }
+
     /// A specialized `Result` type used throughout the UOR Framework.
     pub type UorResult<T> = std::result::Result<T, UorError>;
 
+    /// Small deterministic pseudo-random helpers, so seeded components
+    /// (k-means initialization, kernel weights, ...) are reproducible without
+    /// pulling in an RNG crate.
+    pub(crate) mod rng {
+        /// One step of the SplitMix64 generator.
+        pub(crate) fn splitmix64(state: &mut u64) -> u64 {
+            *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = *state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^ (z >> 31)
+        }
+
+        /// A value uniformly distributed in `[0, 1)`.
+        pub(crate) fn unit_f64(state: &mut u64) -> f64 {
+            (splitmix64(state) >> 11) as f64 / (1u64 << 53) as f64
+        }
+    }
+
     // -----------------------------------------------------------------------
     // 2. Modules Overview
     // -----------------------------------------------------------------------
@@ -80,7 +116,9 @@ pub mod uor_framework {
     /// domain-specific schema. Charts define how manifold graphs, embeddings,
     /// and domain semantics are laid out.
     pub mod chart {
+        use super::manifold::{Manifold, ManifoldNode};
         use super::{UorResult, UorError};
+        use serde_json::{json, Value};
 
         /// Core structure to represent a single Chart in the UOR Framework.
         ///
@@ -88,37 +126,548 @@ pub mod uor_framework {
         ///  - A name or identifier
         ///  - A version or schema ID
         ///  - Arbitrary JSON-based fields representing domain-specific data
+        ///
+        /// The document is parsed once on construction and kept alongside
+        /// `raw_json`; editing `raw_json` afterwards does not update it.
         #[derive(Debug, Clone)]
         pub struct Chart {
             pub name: String,
             pub version: String,
             pub raw_json: String,
+            value: Value,
+            format: ChartFormat,
+        }
+
+        /// The format a chart was authored in, from [`Chart::format`].
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+        pub enum ChartFormat {
+            #[default]
+            Json,
+            Yaml,
         }
 
         impl Chart {
             /// Constructs a new `Chart` from raw JSON data.
+            ///
+            /// Malformed JSON is rejected with its line and column.
             pub fn from_json(name: &str, version: &str, json_data: &str) -> UorResult<Self> {
-                // Validate JSON or simply store it for now
-                // Additional schema checks can be added here
                 if json_data.is_empty() {
                     return Err(UorError::ChartError(
                         "Provided JSON for Chart is empty.".into()
                     ));
                 }
+                let value = serde_json::from_str(json_data).map_err(|e| {
+                    UorError::ChartError(format!(
+                        "Chart {} is not valid JSON at line {}, column {}: {}",
+                        name,
+                        e.line(),
+                        e.column(),
+                        e
+                    ))
+                })?;
                 Ok(Self {
                     name: name.into(),
                     version: version.into(),
                     raw_json: json_data.into(),
+                    value,
+                    format: ChartFormat::Json,
+                })
+            }
+
+            /// Constructs a new `Chart` from YAML. Requires the `yaml`
+            /// feature.
+            ///
+            /// The document is converted to JSON, which becomes `raw_json`,
+            /// so the chart behaves exactly like one built with
+            /// [`Chart::from_json`]. Malformed YAML is rejected with its line
+            /// and column.
+            #[cfg(feature = "yaml")]
+            pub fn from_yaml(name: &str, version: &str, yaml_data: &str) -> UorResult<Self> {
+                if yaml_data.trim().is_empty() {
+                    return Err(UorError::ChartError(
+                        "Provided YAML for Chart is empty.".into()
+                    ));
+                }
+                let value: Value = serde_yaml::from_str(yaml_data).map_err(|e| match e.location() {
+                    Some(at) => UorError::ChartError(format!(
+                        "Chart {} is not valid YAML at line {}, column {}: {}",
+                        name,
+                        at.line(),
+                        at.column(),
+                        e
+                    )),
+                    None => UorError::ChartError(format!("Chart {} is not valid YAML: {}", name, e)),
+                })?;
+                Ok(Self {
+                    name: name.into(),
+                    version: version.into(),
+                    raw_json: value.to_string(),
+                    value,
+                    format: ChartFormat::Yaml,
                 })
             }
 
-            /// Stub method to parse or validate chart data.
-            /// In a real implementation, you'd parse the JSON here,
-            /// checking for required fields, structures, etc.
+            /// The format the chart was authored in. Charts derived from
+            /// another (patched, redacted, ...) keep its format.
+            pub fn format(&self) -> ChartFormat {
+                self.format
+            }
+
+            /// Render a Handlebars template against `context`, then parse the
+            /// output as the chart's JSON. Supports the standard block
+            /// helpers (`{{#each}}`, `{{#if}}`, ...).
+            ///
+            /// Interpolated values are escaped for use inside JSON strings,
+            /// so write `"{{name}}"` for a string field and `{{count}}` for a
+            /// number. Errors if rendering fails or the output isn't JSON.
+            #[cfg(feature = "templates")]
+            pub fn render_template(template: &str, context: &Value, name: &str, version: &str) -> UorResult<Self> {
+                let mut engine = handlebars::Handlebars::new();
+                engine.register_escape_fn(|text| {
+                    let quoted = Value::String(text.to_string()).to_string();
+                    quoted[1..quoted.len() - 1].to_string()
+                });
+                let rendered = engine.render_template(template, context).map_err(|e| {
+                    UorError::ChartError(format!("Cannot render chart template {}: {}", name, e))
+                })?;
+                Chart::from_json(name, version, &rendered)
+            }
+
+            /// Split newline-delimited JSON into one chart per non-blank line,
+            /// named `name_prefix[0]`, `name_prefix[1]`, ... in order.
+            ///
+            /// Each line must be a complete JSON document; a malformed line
+            /// is reported by its 1-based line number. The charts' `version`
+            /// is left empty for the caller to fill in.
+            pub fn from_ndjson(name_prefix: &str, ndjson: &str) -> UorResult<Vec<Chart>> {
+                let mut charts = Vec::new();
+                for (number, line) in ndjson.lines().enumerate() {
+                    let line = line.trim();
+                    if line.is_empty() {
+                        continue;
+                    }
+                    let value = serde_json::from_str(line).map_err(|e| {
+                        UorError::ChartError(format!("Invalid JSON on line {}: {}", number + 1, e))
+                    })?;
+                    charts.push(Chart {
+                        name: format!("{}[{}]", name_prefix, charts.len()),
+                        version: String::new(),
+                        raw_json: line.into(),
+                        value,
+                        format: ChartFormat::Json,
+                    });
+                }
+                Ok(charts)
+            }
+
+            /// Check that `raw_json` is still well-formed JSON, e.g. after it
+            /// was edited by hand.
             pub fn parse(&self) -> UorResult<()> {
-                // Perform schema-specific validations
+                serde_json::from_str::<Value>(&self.raw_json).map_err(|e| {
+                    UorError::ChartError(format!("Chart {} is not valid JSON: {}", self.name, e))
+                })?;
+                Ok(())
+            }
+
+            /// The parsed document.
+            pub fn value(&self) -> &Value {
+                &self.value
+            }
+
+            /// Borrow the value at an RFC 6901 JSON Pointer, or `None` if
+            /// there is none. See [`Chart::get_pointer`].
+            pub fn get_field(&self, pointer: &str) -> Option<&Value> {
+                self.value.pointer(pointer)
+            }
+
+            /// Build a manifold from the chart's `nodes` and `edges`:
+            ///
+            /// ```json
+            /// {"nodes": [{"id": "A", "data": "1"}, {"id": "B"}],
+            ///  "edges": [{"from": "A", "to": "B"}]}
+            /// ```
+            ///
+            /// A node's `data` defaults to `""`, and non-string data is kept
+            /// as its JSON text. Other fields are ignored, and a chart without
+            /// `nodes` gives an empty manifold. Errors if an edge names a
+            /// node that isn't listed.
+            pub fn to_manifold(&self) -> UorResult<Manifold> {
+                let invalid = |what: String| UorError::ChartError(format!("Chart {} {}", self.name, what));
+                let list = |key: &str| -> UorResult<&[Value]> {
+                    match self.value.get(key) {
+                        None => Ok(&[]),
+                        Some(Value::Array(items)) => Ok(items),
+                        Some(_) => Err(invalid(format!("has a `{}` field that is not an array", key))),
+                    }
+                };
+                let text = |entry: &Value, index: usize, kind: &str, key: &str| -> UorResult<String> {
+                    entry.get(key).and_then(Value::as_str).map(String::from).ok_or_else(|| {
+                        invalid(format!("{} {} needs a string `{}`", kind, index, key))
+                    })
+                };
+
+                let mut manifold = Manifold::new();
+                for (index, entry) in list("nodes")?.iter().enumerate() {
+                    let id = text(entry, index, "node", "id")?;
+                    let data = match entry.get("data") {
+                        None => String::new(),
+                        Some(Value::String(data)) => data.clone(),
+                        Some(other) => other.to_string(),
+                    };
+                    manifold.add_node(ManifoldNode::new(&id, &data));
+                }
+                for (index, entry) in list("edges")?.iter().enumerate() {
+                    let from = text(entry, index, "edge", "from")?;
+                    let to = text(entry, index, "edge", "to")?;
+                    if !manifold.nodes.contains_key(&from) || !manifold.nodes.contains_key(&to) {
+                        return Err(invalid(format!(
+                            "edge {} from {} to {} names a node that isn't listed",
+                            index, from, to
+                        )));
+                    }
+                    manifold.add_edge(&from, &to)?;
+                }
+                Ok(manifold)
+            }
+
+            /// Look up a value by RFC 6901 JSON Pointer (e.g.
+            /// `/model/layers/0/size`).
+            ///
+            /// `~1` and `~0` in a reference token unescape to `/` and `~`.
+            /// The empty pointer refers to the whole document.
+            pub fn get_pointer(&self, pointer: &str) -> UorResult<Value> {
+                self.get_field(pointer).cloned().ok_or_else(|| {
+                    UorError::ChartError(format!(
+                        "Chart {} has no value at pointer {}",
+                        self.name, pointer
+                    ))
+                })
+            }
+
+            /// Re-serialize the chart's JSON for human readers, indented by
+            /// two spaces with object keys in sorted order.
+            pub fn to_pretty_json(&self) -> UorResult<String> {
+                serde_json::to_string_pretty(&self.value)
+                    .map_err(|e| UorError::ChartError(format!("Cannot serialize chart: {}", e)))
+            }
+
+            /// Apply an RFC 6902 JSON Patch, returning the patched chart.
+            ///
+            /// Supports `add`, `remove`, `replace`, `move`, `copy` and `test`.
+            /// The patch is all-or-nothing: a failed `test`, a missing path or
+            /// a malformed operation errors and `self` is left untouched.
+            pub fn apply_patch(&self, patch: &str) -> UorResult<Chart> {
+                let operations: Value = serde_json::from_str(patch)
+                    .map_err(|e| UorError::ChartError(format!("Invalid JSON Patch: {}", e)))?;
+                let operations = operations.as_array().ok_or_else(|| {
+                    UorError::ChartError("A JSON Patch must be an array of operations".into())
+                })?;
+
+                let mut document = self.value.clone();
+                for (index, operation) in operations.iter().enumerate() {
+                    json_patch::apply(&mut document, operation).map_err(|e| {
+                        UorError::ChartError(format!("JSON Patch operation {} failed: {}", index, e))
+                    })?;
+                }
+
+                Ok(Chart {
+                    name: self.name.clone(),
+                    version: self.version.clone(),
+                    raw_json: document.to_string(),
+                    value: document,
+                    format: self.format,
+                })
+            }
+
+            /// A copy of the chart with the value at each dotted path (e.g.
+            /// `service.auth.api_key`, array elements by index) replaced by
+            /// `"***"`, so it can be logged safely.
+            ///
+            /// Errors if any path does not exist.
+            pub fn redact(&self, paths: &[&str]) -> UorResult<Chart> {
+                let mut document = self.value.clone();
+                for path in paths {
+                    let target = dotted_mut(&mut document, path).ok_or_else(|| {
+                        UorError::ChartError(format!(
+                            "Chart {} has no value at path {}",
+                            self.name, path
+                        ))
+                    })?;
+                    *target = Value::String("***".into());
+                }
+                Ok(Chart {
+                    name: self.name.clone(),
+                    version: self.version.clone(),
+                    raw_json: document.to_string(),
+                    value: document,
+                    format: self.format,
+                })
+            }
+
+            /// Draft a JSON Schema describing this chart's document.
+            ///
+            /// Best effort from a single instance: every key present is
+            /// marked required, integers and other numbers are told apart,
+            /// and arrays whose elements disagree get an `anyOf` item schema.
+            /// Returned pretty-printed, as a starting point to edit by hand.
+            pub fn infer_schema(&self) -> UorResult<String> {
+                let mut schema = infer_value_schema(&self.value);
+                if let Value::Object(map) = &mut schema {
+                    map.insert(
+                        "$schema".into(),
+                        "https://json-schema.org/draft/2020-12/schema".into(),
+                    );
+                }
+                serde_json::to_string_pretty(&schema)
+                    .map_err(|e| UorError::ChartError(format!("Cannot serialize schema: {}", e)))
+            }
+
+            /// Call `visitor` for every value in the document, parents before
+            /// children, with the RFC 6901 pointer to each (`""` for the
+            /// root). Object members are visited in key order.
+            ///
+            /// Stops at the first error the visitor returns.
+            pub fn walk<V: ChartVisitor>(&self, visitor: &mut V) -> UorResult<()> {
+                fn walk_value<V: ChartVisitor>(pointer: &mut String, value: &Value, visitor: &mut V) -> UorResult<()> {
+                    visitor.visit(pointer, value)?;
+                    let mut child = |pointer: &mut String, token: &str, value: &Value| {
+                        let len = pointer.len();
+                        pointer.push('/');
+                        pointer.push_str(token);
+                        let result = walk_value(pointer, value, visitor);
+                        pointer.truncate(len);
+                        result
+                    };
+                    match value {
+                        Value::Object(map) => {
+                            for (key, value) in map {
+                                child(pointer, &key.replace('~', "~0").replace('/', "~1"), value)?;
+                            }
+                        }
+                        Value::Array(items) => {
+                            for (index, value) in items.iter().enumerate() {
+                                child(pointer, &index.to_string(), value)?;
+                            }
+                        }
+                        _ => {}
+                    }
+                    Ok(())
+                }
+
+                walk_value(&mut String::new(), &self.value, visitor)
+            }
+        }
+
+        /// The schema of a single JSON value; see [`Chart::infer_schema`].
+        fn infer_value_schema(value: &Value) -> Value {
+            match value {
+                Value::Null => json!({ "type": "null" }),
+                Value::Bool(_) => json!({ "type": "boolean" }),
+                Value::Number(n) if n.is_f64() => json!({ "type": "number" }),
+                Value::Number(_) => json!({ "type": "integer" }),
+                Value::String(_) => json!({ "type": "string" }),
+                Value::Array(items) => {
+                    let mut variants: Vec<Value> = Vec::new();
+                    for item in items {
+                        let schema = infer_value_schema(item);
+                        if !variants.contains(&schema) {
+                            variants.push(schema);
+                        }
+                    }
+                    match variants.len() {
+                        0 => json!({ "type": "array" }),
+                        1 => json!({ "type": "array", "items": variants.remove(0) }),
+                        _ => json!({ "type": "array", "items": { "anyOf": variants } }),
+                    }
+                }
+                Value::Object(map) => {
+                    let properties: serde_json::Map<String, Value> = map
+                        .iter()
+                        .map(|(key, value)| (key.clone(), infer_value_schema(value)))
+                        .collect();
+                    let required: Vec<&String> = map.keys().collect();
+                    json!({ "type": "object", "properties": properties, "required": required })
+                }
+            }
+        }
+
+        /// Callback for [`Chart::walk`].
+        pub trait ChartVisitor {
+            /// Called once per JSON value with its pointer path.
+            fn visit(&mut self, pointer: &str, value: &Value) -> UorResult<()>;
+        }
+
+        /// Collects every string leaf of a chart with its pointer path.
+        #[derive(Debug, Clone, Default)]
+        pub struct CollectStrings {
+            pub strings: Vec<(String, String)>,
+        }
+
+        impl ChartVisitor for CollectStrings {
+            fn visit(&mut self, pointer: &str, value: &Value) -> UorResult<()> {
+                if let Value::String(s) = value {
+                    self.strings.push((pointer.to_string(), s.clone()));
+                }
+                Ok(())
+            }
+        }
+
+        /// Resolve a dotted path: object keys by name, array elements by index.
+        fn dotted_mut<'a>(value: &'a mut Value, path: &str) -> Option<&'a mut Value> {
+            path.split('.').try_fold(value, |current, segment| match current {
+                Value::Object(map) => map.get_mut(segment),
+                Value::Array(items) => items.get_mut(segment.parse::<usize>().ok()?),
+                _ => None,
+            })
+        }
+
+        /// RFC 6902 operations over a `serde_json::Value`.
+        mod json_patch {
+            use serde_json::Value;
+
+            /// Split an RFC 6901 pointer into unescaped reference tokens.
+            fn tokens(pointer: &str) -> Result<Vec<String>, String> {
+                if pointer.is_empty() {
+                    return Ok(Vec::new());
+                }
+                let rest = pointer
+                    .strip_prefix('/')
+                    .ok_or_else(|| format!("pointer {:?} must start with '/'", pointer))?;
+                Ok(rest
+                    .split('/')
+                    .map(|token| token.replace("~1", "/").replace("~0", "~"))
+                    .collect())
+            }
+
+            /// Parse an array index token, which may not have leading zeros.
+            fn index(token: &str, len: usize, allow_end: bool) -> Result<usize, String> {
+                if allow_end && token == "-" {
+                    return Ok(len);
+                }
+                let valid = !token.is_empty()
+                    && token.bytes().all(|b| b.is_ascii_digit())
+                    && (token == "0" || !token.starts_with('0'));
+                let i: usize = token
+                    .parse()
+                    .ok()
+                    .filter(|_| valid)
+                    .ok_or_else(|| format!("{:?} is not an array index", token))?;
+                let limit = if allow_end { len } else { len.saturating_sub(1) };
+                if i > limit || (!allow_end && len == 0) {
+                    return Err(format!("array index {} is out of bounds", i));
+                }
+                Ok(i)
+            }
+
+            /// Resolve the container holding the last token of `path`.
+            fn parent<'a>(document: &'a mut Value, path: &[String]) -> Result<&'a mut Value, String> {
+                let mut current = document;
+                for token in &path[..path.len() - 1] {
+                    current = match current {
+                        Value::Object(map) => map
+                            .get_mut(token)
+                            .ok_or_else(|| format!("no member {:?}", token))?,
+                        Value::Array(items) => {
+                            let i = index(token, items.len(), false)?;
+                            &mut items[i]
+                        }
+                        _ => return Err(format!("cannot index into a scalar with {:?}", token)),
+                    };
+                }
+                Ok(current)
+            }
+
+            fn get<'a>(document: &'a Value, pointer: &str) -> Result<&'a Value, String> {
+                document
+                    .pointer(pointer)
+                    .ok_or_else(|| format!("no value at {:?}", pointer))
+            }
+
+            fn add(document: &mut Value, pointer: &str, value: Value) -> Result<(), String> {
+                let path = tokens(pointer)?;
+                let Some(last) = path.last() else {
+                    *document = value;
+                    return Ok(());
+                };
+                match parent(document, &path)? {
+                    Value::Object(map) => {
+                        map.insert(last.clone(), value);
+                    }
+                    Value::Array(items) => {
+                        let i = index(last, items.len(), true)?;
+                        items.insert(i, value);
+                    }
+                    _ => return Err(format!("cannot add a member to a scalar at {:?}", pointer)),
+                }
                 Ok(())
             }
+
+            fn remove(document: &mut Value, pointer: &str) -> Result<Value, String> {
+                let path = tokens(pointer)?;
+                let Some(last) = path.last() else {
+                    return Err("cannot remove the whole document".into());
+                };
+                match parent(document, &path)? {
+                    Value::Object(map) => map
+                        .remove(last)
+                        .ok_or_else(|| format!("no value at {:?}", pointer)),
+                    Value::Array(items) => {
+                        let i = index(last, items.len(), false)?;
+                        Ok(items.remove(i))
+                    }
+                    _ => Err(format!("no value at {:?}", pointer)),
+                }
+            }
+
+            /// Apply a single patch operation object.
+            pub(super) fn apply(document: &mut Value, operation: &Value) -> Result<(), String> {
+                let field = |name: &str| -> Result<&str, String> {
+                    operation
+                        .get(name)
+                        .and_then(Value::as_str)
+                        .ok_or_else(|| format!("missing string field {:?}", name))
+                };
+                let value = || -> Result<Value, String> {
+                    operation
+                        .get("value")
+                        .cloned()
+                        .ok_or_else(|| "missing field \"value\"".to_string())
+                };
+
+                let path = field("path")?;
+                match field("op")? {
+                    "add" => add(document, path, value()?),
+                    "remove" => remove(document, path).map(|_| ()),
+                    "replace" => {
+                        let value = value()?;
+                        let target = document
+                            .pointer_mut(path)
+                            .ok_or_else(|| format!("no value at {:?}", path))?;
+                        *target = value;
+                        Ok(())
+                    }
+                    "move" => {
+                        let from = field("from")?;
+                        if path.starts_with(from) && path[from.len()..].starts_with('/') {
+                            return Err(format!("cannot move {:?} into its own child", from));
+                        }
+                        let moved = remove(document, from)?;
+                        add(document, path, moved)
+                    }
+                    "copy" => {
+                        let copied = get(document, field("from")?)?.clone();
+                        add(document, path, copied)
+                    }
+                    "test" => {
+                        if *get(document, path)? == value()? {
+                            Ok(())
+                        } else {
+                            Err(format!("test failed at {:?}", path))
+                        }
+                    }
+                    other => Err(format!("unknown op {:?}", other)),
+                }
+            }
         }
     }
 
@@ -131,29 +680,188 @@ pub mod uor_framework {
     /// multi-modal data in a graph form.
     pub mod manifold {
         use super::{UorResult, UorError};
-        use std::collections::HashMap;
+        use serde::{Deserialize, Serialize};
+        use serde_json::{json, Value};
+        use std::collections::hash_map::DefaultHasher;
+        use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+        use std::hash::{Hash, Hasher};
+        #[cfg(feature = "petgraph")]
+        use petgraph::graph::{DiGraph, NodeIndex};
+
+        /// Version of the JSON layout written by [`Manifold::to_json`].
+        pub const MANIFOLD_FORMAT_VERSION: u64 = 3;
+
+        /// Largest manifold [`Manifold::is_isomorphic_to`] will search.
+        pub const MAX_ISOMORPHISM_NODES: usize = 12;
+
+        /// Lowest data similarity (0 to 1) at which [`Manifold::align`]
+        /// pairs two nodes whose data differ.
+        pub const ALIGN_MIN_SIMILARITY: f64 = 0.5;
 
         /// Represents a single node in the manifold DAG.
-        #[derive(Debug, Clone)]
+        #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
         pub struct ManifoldNode {
             pub id: String,
             pub data: String,  // or more complex type
+            /// Free-form annotations written by operators (e.g. `"cluster"`).
+            #[serde(default)]
+            pub metadata: HashMap<String, String>,
+        }
+
+        impl ManifoldNode {
+            /// Create a node with the given ID and data and no metadata.
+            pub fn new(id: &str, data: &str) -> Self {
+                Self {
+                    id: id.into(),
+                    data: data.into(),
+                    metadata: HashMap::new(),
+                }
+            }
+
+            /// The node's data as a number, by [`parse_numeric`].
+            pub fn value(&self) -> Option<f64> {
+                parse_numeric(&self.data)
+            }
+        }
+
+        /// One outgoing edge in [`Manifold::edges`]: its target and weight.
+        #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+        pub struct Edge {
+            pub to: String,
+            /// 1.0 unless set. Parallel edges carry their own weights.
+            pub weight: f64,
+        }
+
+        impl Edge {
+            /// An edge to `to` with the default weight of 1.0.
+            pub fn new(to: &str) -> Self {
+                Self::weighted(to, 1.0)
+            }
+
+            /// An edge to `to` with the given weight.
+            pub fn weighted(to: &str, weight: f64) -> Self {
+                Self { to: to.into(), weight }
+            }
+        }
+
+        /// The crate's one rule for reading numbers out of node data: the
+        /// text is trimmed, then must be a finite decimal number with `.`
+        /// as the decimal point and an optional sign and exponent (`" 42 "`,
+        /// `"-0.5"`, `"1e3"`). Anything else, including `"NaN"`, `"inf"`
+        /// and `"1,5"`, is not numeric.
+        pub fn parse_numeric(text: &str) -> Option<f64> {
+            text.trim().parse::<f64>().ok().filter(|v| v.is_finite())
+        }
+
+        /// How alike two nodes' data are, from 0 to 1 (identical). Two
+        /// numbers compare by relative difference, anything else by
+        /// character edit distance relative to the longer text.
+        fn data_similarity(a: &str, b: &str) -> f64 {
+            if let (Some(x), Some(y)) = (parse_numeric(a), parse_numeric(b)) {
+                let scale = x.abs().max(y.abs());
+                return if scale == 0.0 { 1.0 } else { (1.0 - (x - y).abs() / scale).max(0.0) };
+            }
+            let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+            let longest = a.len().max(b.len());
+            if longest == 0 {
+                return 1.0;
+            }
+            // Levenshtein distance, one row at a time.
+            let mut row: Vec<usize> = (0..=b.len()).collect();
+            for (i, ca) in a.iter().enumerate() {
+                let mut diagonal = row[0];
+                row[0] = i + 1;
+                for (j, cb) in b.iter().enumerate() {
+                    let substitute = diagonal + usize::from(ca != cb);
+                    diagonal = row[j + 1];
+                    row[j + 1] = substitute.min(row[j] + 1).min(diagonal + 1);
+                }
+            }
+            1.0 - row[b.len()] as f64 / longest as f64
+        }
+
+        /// Mutable access to a node's data and metadata, from
+        /// [`Manifold::get_node_mut`].
+        ///
+        /// The ID is the node's key in the manifold, so it is read-only here;
+        /// renaming a node means removing and re-adding it.
+        #[derive(Debug)]
+        pub struct ManifoldNodeMut<'a> {
+            id: &'a str,
+            pub data: &'a mut String,
+            pub metadata: &'a mut HashMap<String, String>,
+        }
+
+        impl ManifoldNodeMut<'_> {
+            pub fn id(&self) -> &str {
+                self.id
+            }
+        }
+
+        /// What [`Manifold::merge_with`] does when both manifolds have a node
+        /// with the same ID.
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+        pub enum MergePolicy {
+            /// Fail the merge, leaving the manifold unchanged.
+            #[default]
+            Error,
+            /// Keep this manifold's node.
+            KeepExisting,
+            /// Replace it with the other manifold's node.
+            Overwrite,
+        }
+
+        /// The differences between two manifolds, as reported by
+        /// [`Manifold::diff`].
+        ///
+        /// Each list is sorted so reports are stable across runs. Edges are
+        /// compared as multisets, so a duplicated edge shows up once per
+        /// extra copy.
+        #[derive(Debug, Clone, Default, PartialEq)]
+        pub struct ManifoldDiff {
+            pub added_nodes: Vec<String>,
+            pub removed_nodes: Vec<String>,
+            /// Nodes present in both manifolds whose `data` or `metadata` differs.
+            pub changed_nodes: Vec<String>,
+            pub added_edges: Vec<(String, String)>,
+            pub removed_edges: Vec<(String, String)>,
+        }
+
+        impl ManifoldDiff {
+            /// `true` when the two manifolds were identical.
+            pub fn is_empty(&self) -> bool {
+                self.added_nodes.is_empty()
+                    && self.removed_nodes.is_empty()
+                    && self.changed_nodes.is_empty()
+                    && self.added_edges.is_empty()
+                    && self.removed_edges.is_empty()
+            }
         }
 
         /// Represents the entire DAG, with adjacency relationships.
-        #[derive(Debug, Clone)]
+        #[derive(Debug, Clone, Default)]
         pub struct Manifold {
             pub nodes: HashMap<String, ManifoldNode>,
-            pub edges: HashMap<String, Vec<String>>, // adjacency list
+            pub edges: HashMap<String, Vec<Edge>>, // adjacency list
+            /// Graph-level annotations written by operators.
+            pub metadata: HashMap<String, String>,
+            /// Make [`Manifold::add_edge`] reject edges that would close a
+            /// cycle. Off by default; turning it on doesn't check edges
+            /// already present. Not part of equality or hashing, but saved
+            /// with the manifold.
+            pub strict_dag: bool,
+            /// Keep at most one edge per `from -> to` pair: adding an edge
+            /// that already exists is a no-op, and it keeps its weight. Off
+            /// by default; turning it on doesn't merge parallel edges
+            /// already present. Not part of equality or hashing, but saved
+            /// with the manifold.
+            pub simple: bool,
         }
 
         impl Manifold {
             /// Create an empty Manifold.
             pub fn new() -> Self {
-                Self {
-                    nodes: HashMap::new(),
-                    edges: HashMap::new(),
-                }
+                Self::default()
             }
 
             /// Add a node to the manifold.
@@ -161,189 +869,3928 @@ pub mod uor_framework {
                 self.nodes.insert(node.id.clone(), node);
             }
 
+            /// Edit a node's data or metadata in place.
+            pub fn get_node_mut(&mut self, id: &str) -> UorResult<ManifoldNodeMut<'_>> {
+                let ManifoldNode { id, data, metadata } = self
+                    .nodes
+                    .get_mut(id)
+                    .ok_or_else(|| UorError::General(format!("Node {} not found", id)))?;
+                Ok(ManifoldNodeMut { id: id.as_str(), data, metadata })
+            }
+
             /// Add a directed edge between two existing nodes.
+            ///
+            /// With `strict_dag` set, an edge that would close a cycle
+            /// (including a self-loop) is rejected. With `simple` set, adding
+            /// an existing edge again does nothing.
             pub fn add_edge(&mut self, from: &str, to: &str) -> UorResult<()> {
+                self.add_weighted_edge(from, to, 1.0)
+            }
+
+            /// [`Manifold::add_edge`], giving the new edge a weight.
+            pub fn add_weighted_edge(&mut self, from: &str, to: &str, weight: f64) -> UorResult<()> {
                 if !self.nodes.contains_key(from) || !self.nodes.contains_key(to) {
                     return Err(UorError::General(format!(
                         "Cannot add edge from {} to {}: Node(s) not found",
                         from, to
                     )));
                 }
-                self.edges.entry(from.into()).or_default().push(to.into());
+                if self.strict_dag && self.would_create_cycle(from, to) {
+                    return Err(UorError::General(format!(
+                        "Cannot add edge from {} to {}: it would create a cycle",
+                        from, to
+                    )));
+                }
+                let targets = self.edges.entry(from.into()).or_default();
+                if !(self.simple && targets.iter().any(|edge| edge.to == to)) {
+                    targets.push(Edge::weighted(to, weight));
+                }
                 Ok(())
             }
-        }
-    }
 
-    // 2.3. foundation_model
-    //      Describes the “abstract class” or trait for a UOR Foundation Model.
-    //      This model can be specialized (e.g., for spinor geometry, or for
-    //      language parsing). Each “slot” in the stack references an instance
-    //      of this trait.
+            /// `true` if adding `from -> to` would close a cycle, i.e. `from`
+            /// is `to` or is reachable from it.
+            pub fn would_create_cycle(&self, from: &str, to: &str) -> bool {
+                let mut visited = HashSet::new();
+                let mut stack = vec![to];
+                while let Some(id) = stack.pop() {
+                    if id == from {
+                        return true;
+                    }
+                    if visited.insert(id) {
+                        stack.extend(self.targets(id));
+                    }
+                }
+                false
+            }
 
-    /// The foundation_model module provides traits and definitions for how
-    /// various domain-specific or multi-modal “foundation models” interact
-    /// with manifold data.
-    pub mod foundation_model {
-        use super::manifold::Manifold;
-        use super::UorResult;
+            /// Connect `a` and `b` in both directions.
+            ///
+            /// Like two [`Manifold::add_edge`] calls: with `simple` set,
+            /// directions that already exist are not duplicated, so calling
+            /// this twice leaves a single pair of edges. A pair of opposing
+            /// edges is a cycle, so this always errors with `strict_dag` set.
+            pub fn add_biedge(&mut self, a: &str, b: &str) -> UorResult<()> {
+                if !self.nodes.contains_key(a) || !self.nodes.contains_key(b) {
+                    return Err(UorError::General(format!(
+                        "Cannot add edge between {} and {}: Node(s) not found",
+                        a, b
+                    )));
+                }
+                if self.strict_dag {
+                    return Err(UorError::General(format!(
+                        "Cannot add edge between {} and {}: it would create a cycle",
+                        a, b
+                    )));
+                }
+                for (from, to) in [(a, b), (b, a)] {
+                    let targets = self.edges.entry(from.into()).or_default();
+                    if !(self.simple && targets.iter().any(|edge| edge.to == to)) {
+                        targets.push(Edge::new(to));
+                    }
+                }
+                Ok(())
+            }
 
-        /// Trait that all foundation models must implement.
-        ///
-        /// A FoundationModel can transform manifolds, interpret them,
-        /// or produce new manifolds as output. This is the “abstract class”
-        /// in the UOR design.
-        pub trait FoundationModel {
-            /// Process an input manifold and return a transformed or
-            /// enriched manifold.
-            fn process_manifold(&mut self, input: &Manifold) -> UorResult<Manifold>;
-        }
+            /// Remove every edge from `a` to `b` and from `b` to `a`, weights
+            /// included.
+            ///
+            /// Removing a pair that isn't connected is not an error.
+            pub fn remove_biedge(&mut self, a: &str, b: &str) {
+                for (from, to) in [(a, b), (b, a)] {
+                    if let Some(targets) = self.edges.get_mut(from) {
+                        targets.retain(|edge| edge.to != to);
+                        if targets.is_empty() {
+                            self.edges.remove(from);
+                        }
+                    }
+                }
+            }
 
-        // Optional: provide a default foundation model as a stub
-        // that does nothing. This allows easy testing of "M: Default"
-        // if needed.
-        #[derive(Default)]
-        pub struct NullFoundationModel;
+            /// Remove a node together with every edge into or out of it.
+            ///
+            /// Errors if there is no such node.
+            pub fn remove_node(&mut self, id: &str) -> UorResult<()> {
+                if self.nodes.remove(id).is_none() {
+                    return Err(UorError::General(format!("Node {} not found", id)));
+                }
+                self.edges.remove(id);
+                for targets in self.edges.values_mut() {
+                    targets.retain(|edge| edge.to != id);
+                }
+                self.edges.retain(|_, targets| !targets.is_empty());
+                Ok(())
+            }
 
-        impl FoundationModel for NullFoundationModel {
-            fn process_manifold(&mut self, input: &Manifold) -> UorResult<Manifold> {
-                Ok(input.clone())
+            /// Remove one `from -> to` edge, the first in adjacency order.
+            ///
+            /// Errors if there is no such edge.
+            pub fn remove_edge(&mut self, from: &str, to: &str) -> UorResult<()> {
+                let targets = self
+                    .edges
+                    .get_mut(from)
+                    .ok_or_else(|| UorError::General(format!("No edge from {} to {}", from, to)))?;
+                let position = targets
+                    .iter()
+                    .position(|edge| edge.to == to)
+                    .ok_or_else(|| UorError::General(format!("No edge from {} to {}", from, to)))?;
+                targets.remove(position);
+                if targets.is_empty() {
+                    self.edges.remove(from);
+                }
+                Ok(())
             }
-        }
-    }
 
-    // 2.4. cortex
-    //      Manages the “144 sequential prime reference points” memory space
-    //      for each manifold, storing relevant embeddings or partial expansions.
+            /// Nodes reachable from `start` in breadth-first order, starting
+            /// with `start` itself and following edges in adjacency order.
+            /// Each node is visited once, even with cycles; an unknown start
+            /// yields nothing.
+            pub fn bfs(&self, start: &str) -> impl Iterator<Item = &ManifoldNode> + '_ {
+                Traversal::new(self, start, false)
+            }
 
-    /// The cortex module manages memory-specific data structures for the UOR
-    /// framework, including the “144 prime reference points” used for storing
-    /// manifold embeddings, partial expansions, etc.
-    pub mod cortex {
-        use super::manifold::Manifold;
-        use super::UorResult;
+            /// Like [`Manifold::bfs`], but depth-first (pre-order): each
+            /// node's first out-neighbor is explored fully before the next.
+            pub fn dfs(&self, start: &str) -> impl Iterator<Item = &ManifoldNode> + '_ {
+                Traversal::new(self, start, true)
+            }
 
-        /// A small placeholder for the prime-based memory storage.
-        /// In a real implementation, each “reference point” could hold
-        /// numeric expansions, partial sums, concurrency tasks, etc.
-        #[derive(Debug, Clone)]
-        pub struct PrimeReference {
-            pub prime_index: usize,
-            pub data: Option<f64>, // or more sophisticated field
-        }
+            /// Number of edges leaving `id`, counting parallel edges, or
+            /// `None` if there is no such node.
+            pub fn out_degree(&self, id: &str) -> Option<usize> {
+                self.nodes.contains_key(id).then(|| self.targets(id).count())
+            }
 
-        /// The UOR “cortex” which holds the memory space for a single manifold.
-        #[derive(Debug)]
-        pub struct MemoryCortex {
-            pub references: Vec<PrimeReference>,
-        }
+            /// Number of edges entering `id`, counting parallel edges, or
+            /// `None` if there is no such node. Scans every adjacency list.
+            pub fn in_degree(&self, id: &str) -> Option<usize> {
+                self.nodes.contains_key(id).then(|| {
+                    self.nodes
+                        .keys()
+                        .map(|from| self.targets(from).filter(|to| *to == id).count())
+                        .sum()
+                })
+            }
 
-        // Provide a Default implementation so it can be used in default
-        // constructors of other structures.
-        impl Default for MemoryCortex {
-            fn default() -> Self {
-                Self::new_144()
+            /// IDs of the nodes with no incoming edges, sorted.
+            pub fn roots(&self) -> Vec<String> {
+                let mut has_incoming = HashSet::new();
+                for from in self.nodes.keys() {
+                    has_incoming.extend(self.targets(from));
+                }
+                self.sorted_ids()
+                    .into_iter()
+                    .filter(|id| !has_incoming.contains(id))
+                    .map(String::from)
+                    .collect()
             }
-        }
+
+            /// IDs of the nodes with no outgoing edges, sorted.
+            pub fn leaves(&self) -> Vec<String> {
+                self.sorted_ids()
+                    .into_iter()
+                    .filter(|id| self.targets(id).next().is_none())
+                    .map(String::from)
+                    .collect()
+            }
+
+            /// IDs of the nodes with no edges in or out, sorted. Edges to
+            /// missing nodes don't count; a self-loop does.
+            pub fn orphans(&self) -> Vec<String> {
+                let roots: HashSet<String> = self.roots().into_iter().collect();
+                self.leaves().into_iter().filter(|id| roots.contains(id)).collect()
+            }
+
+            /// Check that the public fields are consistent, e.g. after they
+            /// were edited directly: every edge's endpoints exist and every
+            /// node is stored under its own ID.
+            ///
+            /// Errors listing every problem found, not just the first.
+            pub fn validate(&self) -> UorResult<()> {
+                let mut problems = Vec::new();
+                for id in self.sorted_ids() {
+                    if self.nodes[id].id != id {
+                        problems.push(format!("node stored as {} has ID {}", id, self.nodes[id].id));
+                    }
+                }
+                let mut sources: Vec<&String> = self.edges.keys().collect();
+                sources.sort_unstable();
+                for from in sources {
+                    for Edge { to, .. } in &self.edges[from] {
+                        let missing: Vec<&str> = [from, to]
+                            .into_iter()
+                            .filter(|id| !self.nodes.contains_key(*id))
+                            .map(String::as_str)
+                            .collect();
+                        if !missing.is_empty() {
+                            problems.push(format!(
+                                "edge {}->{} points at missing node {}",
+                                from,
+                                to,
+                                missing.join(" and ")
+                            ));
+                        }
+                    }
+                }
+                if !problems.is_empty() {
+                    return Err(UorError::General(format!("Manifold is invalid: {}", problems.join("; "))));
+                }
+                Ok(())
+            }
+
+            /// Contract the edge `from -> to`, merging `to` into `from`; see
+            /// [`Manifold::contract_edge_with`]. `from` keeps its own data.
+            pub fn contract_edge(&mut self, from: &str, to: &str) -> UorResult<()> {
+                self.contract_edge_with(from, to, |from_data, _| from_data.to_string())
+            }
+
+            /// Contract the edge `from -> to`: `to` is removed and its edges
+            /// are redirected to `from`, whose data becomes
+            /// `merge(from_data, to_data)`.
+            ///
+            /// Every edge between the two nodes (either way) disappears
+            /// rather than becoming a self-loop, and `from` keeps its
+            /// metadata. Redirected edges keep their weights. Errors if there
+            /// is no such edge, or if the result would be cyclic with `strict_dag` set;
+            /// the manifold is unchanged on error.
+            pub fn contract_edge_with(
+                &mut self,
+                from: &str,
+                to: &str,
+                merge: impl Fn(&str, &str) -> String,
+            ) -> UorResult<()> {
+                let connected = from != to && self.targets(from).any(|t| t == to);
+                if !connected {
+                    return Err(UorError::General(format!("No edge from {} to {} to contract", from, to)));
+                }
+
+                let mut result = self.clone();
+                let removed = result.nodes.remove(to).expect("edge targets exist");
+                let outgoing = result.edges.remove(to).unwrap_or_default();
+
+                for (source, targets) in result.edges.iter_mut() {
+                    if source == from {
+                        targets.retain(|edge| edge.to != to);
+                    } else {
+                        targets.iter_mut().filter(|edge| edge.to == to).for_each(|edge| edge.to = from.to_string());
+                    }
+                }
+                let redirected = outgoing.into_iter().filter(|edge| edge.to != from && edge.to != to);
+                result.edges.entry(from.into()).or_default().extend(redirected);
+                result.edges.retain(|_, targets| !targets.is_empty());
+
+                let survivor = result.nodes.get_mut(from).expect("edge sources exist");
+                survivor.data = merge(&survivor.data, &removed.data);
+
+                if result.strict_dag && result.find_cycle().is_some() {
+                    return Err(UorError::General(format!(
+                        "Contracting {} -> {} would create a cycle",
+                        from, to
+                    )));
+                }
+                *self = result;
+                Ok(())
+            }
+
+            /// The subgraph induced by the nodes matching `pred`: those nodes,
+            /// every edge between two of them (with its weight), and the
+            /// manifold's metadata.
+            pub fn subgraph_where(&self, pred: impl Fn(&ManifoldNode) -> bool) -> Manifold {
+                let mut subgraph = Manifold {
+                    metadata: self.metadata.clone(),
+                    strict_dag: self.strict_dag,
+                    simple: self.simple,
+                    ..Manifold::new()
+                };
+                for node in self.nodes.values().filter(|node| pred(node)) {
+                    subgraph.nodes.insert(node.id.clone(), node.clone());
+                }
+                for (from, targets) in &self.edges {
+                    if !subgraph.nodes.contains_key(from) {
+                        continue;
+                    }
+                    let kept: Vec<Edge> = targets
+                        .iter()
+                        .filter(|edge| subgraph.nodes.contains_key(&edge.to))
+                        .cloned()
+                        .collect();
+                    if kept.is_empty() {
+                        continue;
+                    }
+                    subgraph.edges.insert(from.clone(), kept);
+                }
+                subgraph
+            }
+
+            /// Set the weight of every edge from `from` to `to`.
+            ///
+            /// Errors if there is no such edge.
+            pub fn set_edge_weight(&mut self, from: &str, to: &str, weight: f64) -> UorResult<()> {
+                let mut connected = false;
+                for edge in self.edges.get_mut(from).into_iter().flatten().filter(|edge| edge.to == to) {
+                    edge.weight = weight;
+                    connected = true;
+                }
+                if !connected {
+                    return Err(UorError::General(format!("No edge from {} to {}", from, to)));
+                }
+                Ok(())
+            }
+
+            /// The weight of the first edge from `from` to `to`, or 1.0 if
+            /// there is none.
+            pub fn edge_weight(&self, from: &str, to: &str) -> f64 {
+                self.edges
+                    .get(from)
+                    .and_then(|targets| targets.iter().find(|edge| edge.to == to))
+                    .map_or(1.0, |edge| edge.weight)
+            }
+
+            /// Insert every edge that `add_edge` accepts, returning the pairs
+            /// it rejected (e.g. dangling endpoints) instead of erroring.
+            ///
+            /// Intended for best-effort import from noisy data.
+            pub fn add_edges_lenient<I, S>(&mut self, edges: I) -> Vec<(String, String)>
+            where
+                I: IntoIterator<Item = (S, S)>,
+                S: AsRef<str>,
+            {
+                let mut rejected = Vec::new();
+                for (from, to) in edges {
+                    let (from, to) = (from.as_ref(), to.as_ref());
+                    if self.add_edge(from, to).is_err() {
+                        rejected.push((from.to_string(), to.to_string()));
+                    }
+                }
+                rejected
+            }
+
+            /// Insert all of `other`'s nodes and edges, erroring on a node ID
+            /// present in both. See [`Manifold::merge_with`].
+            pub fn merge(&mut self, other: &Manifold) -> UorResult<()> {
+                self.merge_with(other, MergePolicy::Error)
+            }
+
+            /// Insert all of `other`'s nodes, settling ID collisions by
+            /// `policy`, then its edges and their weights. Edges go in
+            /// through `add_edge`, so one with an endpoint missing from the
+            /// merged graph is skipped, as is one whose `from -> to` pair
+            /// this manifold already has (so merging a copy of a manifold
+            /// into it changes nothing). Graph-level metadata is not merged.
+            ///
+            /// On error (a collision under [`MergePolicy::Error`], or with
+            /// `strict_dag` set an edge that would close a cycle) the
+            /// manifold is left unchanged.
+            pub fn merge_with(&mut self, other: &Manifold, policy: MergePolicy) -> UorResult<()> {
+                if policy == MergePolicy::Error {
+                    if let Some(id) = other.sorted_ids().into_iter().find(|id| self.nodes.contains_key(*id)) {
+                        return Err(UorError::General(format!("Cannot merge: node {} exists in both manifolds", id)));
+                    }
+                }
+
+                let existing = self.edge_counts();
+                let mut merged = self.clone();
+                for node in other.nodes.values() {
+                    if policy == MergePolicy::Overwrite || !merged.nodes.contains_key(&node.id) {
+                        merged.nodes.insert(node.id.clone(), node.clone());
+                    }
+                }
+                for from in other.sorted_ids() {
+                    for edge in other.edges.get(from).into_iter().flatten() {
+                        if !merged.nodes.contains_key(&edge.to) || existing.contains_key(&(from, edge.to.as_str())) {
+                            continue;
+                        }
+                        merged.add_weighted_edge(from, &edge.to, edge.weight)?;
+                    }
+                }
+                *self = merged;
+                Ok(())
+            }
+
+            /// A copy of the manifold with every node but only the edges for
+            /// which `pred(from, to)` holds. The original is not modified.
+            pub fn filter_edges(&self, pred: impl Fn(&str, &str) -> bool) -> Manifold {
+                let mut filtered = self.clone();
+                for (from, targets) in filtered.edges.iter_mut() {
+                    targets.retain(|edge| pred(from, &edge.to));
+                }
+                filtered.edges.retain(|_, targets| !targets.is_empty());
+                filtered
+            }
+
+            /// The data of node `id` as a number, by [`parse_numeric`], or
+            /// `None` if it isn't numeric. Errors if there is no such node.
+            pub fn node_value(&self, id: &str) -> UorResult<Option<f64>> {
+                self.nodes
+                    .get(id)
+                    .map(ManifoldNode::value)
+                    .ok_or_else(|| UorError::General(format!("Node {} not found", id)))
+            }
+
+            /// How many nodes share each `data` string.
+            pub fn data_histogram(&self) -> HashMap<String, usize> {
+                let mut counts = HashMap::new();
+                for node in self.nodes.values() {
+                    *counts.entry(node.data.clone()).or_insert(0) += 1;
+                }
+                counts
+            }
+
+            /// Report what changed going from `self` to `other`.
+            ///
+            /// "Added" means present in `other` but not in `self`, so the
+            /// usual call is `before.diff(&after)`.
+            pub fn diff(&self, other: &Manifold) -> ManifoldDiff {
+                let mut diff = ManifoldDiff::default();
+
+                for (id, node) in &self.nodes {
+                    match other.nodes.get(id) {
+                        None => diff.removed_nodes.push(id.clone()),
+                        Some(theirs)
+                            if theirs.data != node.data || theirs.metadata != node.metadata =>
+                        {
+                            diff.changed_nodes.push(id.clone())
+                        }
+                        Some(_) => {}
+                    }
+                }
+                for id in other.nodes.keys() {
+                    if !self.nodes.contains_key(id) {
+                        diff.added_nodes.push(id.clone());
+                    }
+                }
+
+                let mine = self.edge_counts();
+                let theirs = other.edge_counts();
+                for (edge, &count) in &mine {
+                    let remaining = theirs.get(edge).copied().unwrap_or(0);
+                    for _ in remaining..count {
+                        diff.removed_edges.push((edge.0.to_string(), edge.1.to_string()));
+                    }
+                }
+                for (edge, &count) in &theirs {
+                    let existing = mine.get(edge).copied().unwrap_or(0);
+                    for _ in existing..count {
+                        diff.added_edges.push((edge.0.to_string(), edge.1.to_string()));
+                    }
+                }
+
+                diff.added_nodes.sort();
+                diff.removed_nodes.sort();
+                diff.changed_nodes.sort();
+                diff.added_edges.sort();
+                diff.removed_edges.sort();
+                diff
+            }
+
+            /// A cheap upper bound on the structural graph edit distance to
+            /// `other`: nodes and edges present in only one of the two, when
+            /// nodes are matched by ID.
+            ///
+            /// The true distance can be lower when a better node matching
+            /// exists. Data and metadata changes are not counted.
+            pub fn edit_distance_upper_bound(&self, other: &Manifold) -> usize {
+                let diff = self.diff(other);
+                diff.added_nodes.len()
+                    + diff.removed_nodes.len()
+                    + diff.added_edges.len()
+                    + diff.removed_edges.len()
+            }
+
+            /// Pair each node of `self` with its counterpart in `other`, e.g.
+            /// to compare the two manifolds' embeddings node by node.
+            ///
+            /// Nodes with the same ID are paired first. Each remaining node of
+            /// `self` is then paired with the remaining node of `other` with
+            /// identical data (the lowest such ID), if there is one. Last,
+            /// still-unpaired nodes of `self`, in ID order, each take the
+            /// remaining node of `other` whose data is most similar (numeric
+            /// closeness, or edit distance for text), if that similarity is
+            /// at least [`ALIGN_MIN_SIMILARITY`].
+            ///
+            /// Pairs follow `self`'s ID order, with a `None` partner for
+            /// unmatched nodes; `other`'s unmatched nodes come last, in ID
+            /// order, as `(None, Some(id))`.
+            pub fn align(&self, other: &Manifold) -> Vec<(Option<String>, Option<String>)> {
+                let mut by_data: HashMap<&str, VecDeque<&str>> = HashMap::new();
+                for id in other.sorted_ids() {
+                    if !self.nodes.contains_key(id) {
+                        by_data.entry(other.nodes[id].data.as_str()).or_default().push_back(id);
+                    }
+                }
+
+                let mut partners: Vec<(&str, Option<&str>)> = Vec::with_capacity(self.nodes.len());
+                for id in self.sorted_ids() {
+                    let partner = if other.nodes.contains_key(id) {
+                        Some(id)
+                    } else {
+                        by_data.get_mut(self.nodes[id].data.as_str()).and_then(VecDeque::pop_front)
+                    };
+                    partners.push((id, partner));
+                }
+
+                let mut unmatched: BTreeSet<&str> = by_data.into_values().flatten().collect();
+                for (id, partner) in partners.iter_mut().filter(|(_, partner)| partner.is_none()) {
+                    let data = &self.nodes[*id].data;
+                    let mut best: Option<(f64, &str)> = None;
+                    for &candidate in &unmatched {
+                        let similarity = data_similarity(data, &other.nodes[candidate].data);
+                        // `unmatched` is in ID order, so ties keep the lowest ID.
+                        if similarity >= ALIGN_MIN_SIMILARITY && best.is_none_or(|(top, _)| similarity > top) {
+                            best = Some((similarity, candidate));
+                        }
+                    }
+                    if let Some((_, candidate)) = best {
+                        unmatched.remove(candidate);
+                        *partner = Some(candidate);
+                    }
+                }
+
+                let mut pairs: Vec<(Option<String>, Option<String>)> = partners
+                    .into_iter()
+                    .map(|(id, partner)| (Some(id.to_string()), partner.map(String::from)))
+                    .collect();
+                pairs.extend(unmatched.into_iter().map(|id| (None, Some(id.to_string()))));
+                pairs
+            }
+
+            /// Node IDs in sorted order, for deterministic traversals.
+            pub(crate) fn sorted_ids(&self) -> Vec<&str> {
+                let mut ids: Vec<&str> = self.nodes.keys().map(String::as_str).collect();
+                ids.sort_unstable();
+                ids
+            }
+
+            /// Out-neighbors of `id` that resolve to existing nodes.
+            pub(crate) fn targets<'a>(&'a self, id: &str) -> impl Iterator<Item = &'a str> + 'a {
+                self.weighted_targets(id).map(|(to, _)| to)
+            }
+
+            /// [`Manifold::targets`], paired with each edge's weight.
+            pub(crate) fn weighted_targets<'a>(&'a self, id: &str) -> impl Iterator<Item = (&'a str, f64)> + 'a {
+                self.edges
+                    .get(id)
+                    .into_iter()
+                    .flatten()
+                    .filter(move |edge| self.nodes.contains_key(&edge.to))
+                    .map(|edge| (edge.to.as_str(), edge.weight))
+            }
+
+            /// Strongly-connected components (Tarjan's algorithm).
+            ///
+            /// Each component's IDs are sorted, and components are ordered by
+            /// their smallest member.
+            pub fn strongly_connected_components(&self) -> Vec<Vec<String>> {
+                struct Tarjan<'a> {
+                    graph: &'a Manifold,
+                    next_index: usize,
+                    index: HashMap<&'a str, usize>,
+                    low: HashMap<&'a str, usize>,
+                    stack: Vec<&'a str>,
+                    on_stack: HashSet<&'a str>,
+                    components: Vec<Vec<String>>,
+                }
+
+                impl<'a> Tarjan<'a> {
+                    fn visit(&mut self, v: &'a str) {
+                        self.index.insert(v, self.next_index);
+                        self.low.insert(v, self.next_index);
+                        self.next_index += 1;
+                        self.stack.push(v);
+                        self.on_stack.insert(v);
+
+                        for w in self.graph.targets(v) {
+                            if !self.index.contains_key(w) {
+                                self.visit(w);
+                                let low = self.low[v].min(self.low[w]);
+                                self.low.insert(v, low);
+                            } else if self.on_stack.contains(w) {
+                                let low = self.low[v].min(self.index[w]);
+                                self.low.insert(v, low);
+                            }
+                        }
+
+                        if self.low[v] == self.index[v] {
+                            let mut component = Vec::new();
+                            while let Some(w) = self.stack.pop() {
+                                self.on_stack.remove(w);
+                                component.push(w.to_string());
+                                if w == v {
+                                    break;
+                                }
+                            }
+                            component.sort();
+                            self.components.push(component);
+                        }
+                    }
+                }
+
+                let mut tarjan = Tarjan {
+                    graph: self,
+                    next_index: 0,
+                    index: HashMap::new(),
+                    low: HashMap::new(),
+                    stack: Vec::new(),
+                    on_stack: HashSet::new(),
+                    components: Vec::new(),
+                };
+                for id in self.sorted_ids() {
+                    if !tarjan.index.contains_key(id) {
+                        tarjan.visit(id);
+                    }
+                }
+
+                let mut components = tarjan.components;
+                components.sort();
+                components
+            }
+
+            /// Weakly-connected components: nodes joined by edges in either
+            /// direction.
+            ///
+            /// Ordered like [`Manifold::strongly_connected_components`]: IDs
+            /// sorted within each component, components by smallest member.
+            pub fn weakly_connected_components(&self) -> Vec<Vec<String>> {
+                let neighbors = self.undirected_neighbors();
+                let mut seen = HashSet::new();
+                let mut components = Vec::new();
+                for start in self.sorted_ids() {
+                    if !seen.insert(start) {
+                        continue;
+                    }
+                    let mut component = vec![start.to_string()];
+                    let mut queue = VecDeque::from([start]);
+                    while let Some(id) = queue.pop_front() {
+                        for &next in neighbors.get(id).into_iter().flatten() {
+                            if seen.insert(next) {
+                                component.push(next.to_string());
+                                queue.push_back(next);
+                            }
+                        }
+                    }
+                    component.sort();
+                    components.push(component);
+                }
+                components
+            }
+
+            /// Nodes whose removal would split their weakly-connected
+            /// component, i.e. the cut vertices of the undirected view,
+            /// sorted by ID.
+            pub fn articulation_points(&self) -> Vec<String> {
+                let neighbors = self.undirected_neighbors();
+                let mut discovery: HashMap<&str, usize> = HashMap::new();
+                let mut low: HashMap<&str, usize> = HashMap::new();
+                let mut points = HashSet::new();
+
+                for root in self.sorted_ids() {
+                    if discovery.contains_key(root) {
+                        continue;
+                    }
+                    discovery.insert(root, discovery.len());
+                    low.insert(root, discovery[root]);
+                    let mut root_children = 0;
+                    // (node, its DFS parent, index of the next neighbor to try)
+                    let mut stack: Vec<(&str, Option<&str>, usize)> = vec![(root, None, 0)];
+                    while let Some(&mut (id, parent, ref mut next)) = stack.last_mut() {
+                        let adjacent = neighbors.get(id).map(Vec::as_slice).unwrap_or(&[]);
+                        if let Some(&to) = adjacent.get(*next) {
+                            *next += 1;
+                            if Some(to) == parent {
+                                continue;
+                            }
+                            if let Some(&seen) = discovery.get(to) {
+                                let current = low[id].min(seen);
+                                low.insert(id, current);
+                            } else {
+                                discovery.insert(to, discovery.len());
+                                low.insert(to, discovery[to]);
+                                if id == root {
+                                    root_children += 1;
+                                }
+                                stack.push((to, Some(id), 0));
+                            }
+                            continue;
+                        }
+                        stack.pop();
+                        if let Some(parent) = parent {
+                            let merged = low[parent].min(low[id]);
+                            low.insert(parent, merged);
+                            if parent != root && low[id] >= discovery[parent] {
+                                points.insert(parent);
+                            }
+                        }
+                    }
+                    if root_children > 1 {
+                        points.insert(root);
+                    }
+                }
+
+                let mut points: Vec<String> = points.into_iter().map(String::from).collect();
+                points.sort();
+                points
+            }
+
+            /// The number of weakly-connected components, counted with
+            /// union-find rather than by building each component as
+            /// [`Manifold::weakly_connected_components`] does.
+            pub fn num_weakly_connected_components(&self) -> usize {
+                fn root(parent: &mut [usize], mut i: usize) -> usize {
+                    while parent[i] != i {
+                        parent[i] = parent[parent[i]];
+                        i = parent[i];
+                    }
+                    i
+                }
+
+                let index: HashMap<&str, usize> = self
+                    .nodes
+                    .keys()
+                    .enumerate()
+                    .map(|(i, id)| (id.as_str(), i))
+                    .collect();
+                let mut parent: Vec<usize> = (0..index.len()).collect();
+                let mut components = index.len();
+                for (from, targets) in &self.edges {
+                    let Some(&a) = index.get(from.as_str()) else {
+                        continue;
+                    };
+                    for edge in targets {
+                        let Some(&b) = index.get(edge.to.as_str()) else {
+                            continue;
+                        };
+                        let (a, b) = (root(&mut parent, a), root(&mut parent, b));
+                        if a != b {
+                            parent[a] = b;
+                            components -= 1;
+                        }
+                    }
+                }
+                components
+            }
+
+            /// Find one directed cycle, returned as `[v0, v1, ..., vk]` where
+            /// each node has an edge to the next and `vk -> v0` closes it.
+            ///
+            /// The search visits nodes in sorted order and edges in adjacency
+            /// order, so the same graph always yields the same cycle.
+            pub fn find_cycle(&self) -> Option<Vec<String>> {
+                #[derive(Clone, Copy, PartialEq)]
+                enum Color {
+                    InProgress,
+                    Done,
+                }
+
+                fn visit<'a>(
+                    graph: &'a Manifold,
+                    id: &'a str,
+                    color: &mut HashMap<&'a str, Color>,
+                    path: &mut Vec<&'a str>,
+                ) -> Option<Vec<String>> {
+                    color.insert(id, Color::InProgress);
+                    path.push(id);
+                    for to in graph.targets(id) {
+                        match color.get(to) {
+                            Some(Color::InProgress) => {
+                                let start = path.iter().position(|&p| p == to).expect("on path");
+                                return Some(path[start..].iter().map(|p| p.to_string()).collect());
+                            }
+                            Some(Color::Done) => {}
+                            None => {
+                                if let Some(cycle) = visit(graph, to, color, path) {
+                                    return Some(cycle);
+                                }
+                            }
+                        }
+                    }
+                    path.pop();
+                    color.insert(id, Color::Done);
+                    None
+                }
+
+                let mut color = HashMap::new();
+                for id in self.sorted_ids() {
+                    if !color.contains_key(id) {
+                        if let Some(cycle) = visit(self, id, &mut color, &mut Vec::new()) {
+                            return Some(cycle);
+                        }
+                    }
+                }
+                None
+            }
+
+            /// `true` if the manifold has no directed cycles (self-loops count
+            /// as cycles).
+            pub fn is_acyclic(&self) -> bool {
+                let has_self_loop = self
+                    .nodes
+                    .keys()
+                    .any(|id| self.targets(id).any(|to| to == id));
+                !has_self_loop
+                    && self
+                        .strongly_connected_components()
+                        .iter()
+                        .all(|component| component.len() == 1)
+            }
+
+            /// Collapse each strongly-connected component into a single node.
+            ///
+            /// A component's node ID is its sorted member IDs joined with `,`
+            /// and its data is the member count. An edge joins two components
+            /// when any edge joins their members, so the result is always
+            /// acyclic.
+            pub fn condensation(&self) -> Manifold {
+                let components = self.strongly_connected_components();
+                let mut component_of: HashMap<&str, String> = HashMap::new();
+                let mut condensed = Manifold::new();
+
+                for component in &components {
+                    let id = component.join(",");
+                    for member in component {
+                        component_of.insert(member.as_str(), id.clone());
+                    }
+                    condensed.add_node(ManifoldNode::new(&id, &component.len().to_string()));
+                }
+
+                for from in self.sorted_ids() {
+                    let source = &component_of[from];
+                    for to in self.targets(from) {
+                        let target = &component_of[to];
+                        let known = condensed.edges.get(source).is_some_and(|t| t.iter().any(|edge| &edge.to == target));
+                        if source != target && !known {
+                            condensed
+                                .edges
+                                .entry(source.clone())
+                                .or_default()
+                                .push(Edge::new(target));
+                        }
+                    }
+                }
+                condensed
+            }
+
+            /// Node IDs in dependency order: every edge points from an
+            /// earlier node to a later one.
+            ///
+            /// Kahn's algorithm over the adjacency lists (edges to missing
+            /// nodes are ignored), breaking ties by node ID so the order is
+            /// reproducible. Errors on a cycle, naming the nodes that could
+            /// not be ordered.
+            pub fn topological_sort(&self) -> UorResult<Vec<String>> {
+                Ok(self.topological_order()?.into_iter().map(String::from).collect())
+            }
+
+            /// [`Manifold::topological_sort`], borrowing the IDs.
+            pub(crate) fn topological_order(&self) -> UorResult<Vec<&str>> {
+                let mut in_degree: HashMap<&str, usize> =
+                    self.nodes.keys().map(|id| (id.as_str(), 0)).collect();
+                for id in self.nodes.keys() {
+                    for to in self.targets(id) {
+                        *in_degree.get_mut(to).expect("targets are existing nodes") += 1;
+                    }
+                }
+
+                let mut ready: BTreeSet<&str> = in_degree
+                    .iter()
+                    .filter(|(_, &degree)| degree == 0)
+                    .map(|(&id, _)| id)
+                    .collect();
+                let mut order = Vec::with_capacity(self.nodes.len());
+                while let Some(id) = ready.pop_first() {
+                    order.push(id);
+                    for to in self.targets(id) {
+                        let degree = in_degree.get_mut(to).expect("targets are existing nodes");
+                        *degree -= 1;
+                        if *degree == 0 {
+                            ready.insert(to);
+                        }
+                    }
+                }
+
+                if order.len() < self.nodes.len() {
+                    let mut stuck: Vec<&str> = in_degree
+                        .into_iter()
+                        .filter(|(_, degree)| *degree > 0)
+                        .map(|(id, _)| id)
+                        .collect();
+                    stuck.sort_unstable();
+                    return Err(UorError::General(format!(
+                        "Manifold contains a cycle through: {}",
+                        stuck.join(", ")
+                    )));
+                }
+                Ok(order)
+            }
+
+            /// Hop distance from the nearest of `starts` to every reachable
+            /// node, following edge direction. Starts are at distance 0.
+            ///
+            /// Errors if any start node is missing.
+            pub fn multi_source_bfs(&self, starts: &[&str]) -> UorResult<HashMap<String, usize>> {
+                let mut distance: HashMap<String, usize> = HashMap::new();
+                let mut queue = VecDeque::new();
+                for &start in starts {
+                    if !self.nodes.contains_key(start) {
+                        return Err(UorError::General(format!("Start node {} not found", start)));
+                    }
+                    if !distance.contains_key(start) {
+                        distance.insert(start.to_string(), 0);
+                        queue.push_back(start);
+                    }
+                }
+
+                while let Some(id) = queue.pop_front() {
+                    let next = distance[id] + 1;
+                    for to in self.targets(id) {
+                        if !distance.contains_key(to) {
+                            distance.insert(to.to_string(), next);
+                            queue.push_back(to);
+                        }
+                    }
+                }
+                Ok(distance)
+            }
+
+            /// The longest path in the DAG, as a node ID sequence. A path's
+            /// length is the sum of its edges' weights, which is its hop count
+            /// while every weight is the default 1.0.
+            ///
+            /// Among equally long paths the one ending at the smallest ID wins.
+            /// Errors if the manifold has a cycle.
+            pub fn longest_path(&self) -> UorResult<Vec<String>> {
+                let order = self.topological_order()?;
+                let mut length: HashMap<&str, f64> = HashMap::new();
+                let mut previous: HashMap<&str, &str> = HashMap::new();
+
+                for &id in &order {
+                    let here = *length.entry(id).or_insert(0.0);
+                    for (to, weight) in self.weighted_targets(id) {
+                        let there = length.entry(to).or_insert(0.0);
+                        if here + weight > *there {
+                            *there = here + weight;
+                            previous.insert(to, id);
+                        }
+                    }
+                }
+
+                let end = order.iter().copied().max_by(|a, b| {
+                    length[a].total_cmp(&length[b]).then_with(|| b.cmp(a))
+                });
+                let mut path = Vec::new();
+                let mut cursor = end;
+                while let Some(id) = cursor {
+                    path.push(id.to_string());
+                    cursor = previous.get(id).copied();
+                }
+                path.reverse();
+                Ok(path)
+            }
+
+            /// Each node's layer: 0 for sources, otherwise one more than its
+            /// deepest predecessor, so every edge points to a deeper layer.
+            ///
+            /// Errors if the manifold has a cycle.
+            pub fn layer_assignment(&self) -> UorResult<HashMap<String, usize>> {
+                let mut depth: HashMap<&str, usize> = HashMap::new();
+                for id in self.topological_order()? {
+                    let here = *depth.entry(id).or_insert(0);
+                    for to in self.targets(id) {
+                        let there = depth.entry(to).or_insert(0);
+                        *there = (*there).max(here + 1);
+                    }
+                }
+                Ok(depth.into_iter().map(|(id, d)| (id.to_string(), d)).collect())
+            }
+
+            /// `true` if `other` is this manifold with nodes renamed: some
+            /// bijection between the node sets preserves every edge (with
+            /// multiplicity). Data, metadata and weights are ignored.
+            ///
+            /// Backtracking search, meant for small graphs in tests: above
+            /// [`MAX_ISOMORPHISM_NODES`] nodes it gives up and returns `false`.
+            pub fn is_isomorphic_to(&self, other: &Manifold) -> bool {
+                self.isomorphic(other, false)
+            }
+
+            /// Like [`Manifold::is_isomorphic_to`], but matched nodes must
+            /// also carry the same `data`.
+            pub fn is_isomorphic_with_data(&self, other: &Manifold) -> bool {
+                self.isomorphic(other, true)
+            }
+
+            fn isomorphic(&self, other: &Manifold, match_data: bool) -> bool {
+                struct Search<'a> {
+                    left: &'a Manifold,
+                    right: &'a Manifold,
+                    left_ids: Vec<&'a str>,
+                    right_ids: Vec<&'a str>,
+                    left_edges: HashMap<(&'a str, &'a str), usize>,
+                    right_edges: HashMap<(&'a str, &'a str), usize>,
+                    match_data: bool,
+                    mapping: Vec<usize>,
+                    used: Vec<bool>,
+                }
+
+                impl Search<'_> {
+                    fn degrees(edges: &HashMap<(&str, &str), usize>, id: &str) -> (usize, usize) {
+                        edges.iter().fold((0, 0), |(out, inc), (&(from, to), &n)| {
+                            (out + if from == id { n } else { 0 }, inc + if to == id { n } else { 0 })
+                        })
+                    }
+
+                    fn compatible(&self, i: usize, j: usize) -> bool {
+                        let (a, b) = (self.left_ids[i], self.right_ids[j]);
+                        if self.match_data && self.left.nodes[a].data != self.right.nodes[b].data {
+                            return false;
+                        }
+                        if Self::degrees(&self.left_edges, a) != Self::degrees(&self.right_edges, b) {
+                            return false;
+                        }
+                        // Edges between `a` and every already-mapped node (and itself).
+                        (0..=i).all(|k| {
+                            let (c, d) = if k == i { (a, b) } else { (self.left_ids[k], self.right_ids[self.mapping[k]]) };
+                            let count = |edges: &HashMap<(&str, &str), usize>, x: &str, y: &str| {
+                                edges.get(&(x, y)).copied().unwrap_or(0)
+                            };
+                            count(&self.left_edges, a, c) == count(&self.right_edges, b, d)
+                                && count(&self.left_edges, c, a) == count(&self.right_edges, d, b)
+                        })
+                    }
+
+                    fn extend(&mut self, i: usize) -> bool {
+                        if i == self.left_ids.len() {
+                            return true;
+                        }
+                        for j in 0..self.right_ids.len() {
+                            if self.used[j] || !self.compatible(i, j) {
+                                continue;
+                            }
+                            self.used[j] = true;
+                            self.mapping.push(j);
+                            if self.extend(i + 1) {
+                                return true;
+                            }
+                            self.mapping.pop();
+                            self.used[j] = false;
+                        }
+                        false
+                    }
+                }
+
+                fn resolvable_edges(m: &Manifold) -> HashMap<(&str, &str), usize> {
+                    let mut counts = HashMap::new();
+                    for from in m.sorted_ids() {
+                        for to in m.targets(from) {
+                            *counts.entry((from, to)).or_insert(0) += 1;
+                        }
+                    }
+                    counts
+                }
+
+                let n = self.nodes.len();
+                if n != other.nodes.len() || n > MAX_ISOMORPHISM_NODES {
+                    return false;
+                }
+                let mut search = Search {
+                    left: self,
+                    right: other,
+                    left_ids: self.sorted_ids(),
+                    right_ids: other.sorted_ids(),
+                    left_edges: resolvable_edges(self),
+                    right_edges: resolvable_edges(other),
+                    match_data,
+                    mapping: Vec::with_capacity(n),
+                    used: vec![false; n],
+                };
+                let edge_total = |edges: &HashMap<(&str, &str), usize>| edges.values().sum::<usize>();
+                if edge_total(&search.left_edges) != edge_total(&search.right_edges) {
+                    return false;
+                }
+                search.extend(0)
+            }
+
+            /// Nodes grouped into generations: the first holds the sources,
+            /// and each later one the nodes whose predecessors all sit in
+            /// earlier generations. Nodes within a generation don't depend on
+            /// each other, so each can be processed in parallel.
+            ///
+            /// IDs are sorted within a generation. Errors if the manifold has
+            /// a cycle.
+            pub fn topological_generations(&self) -> UorResult<Vec<Vec<String>>> {
+                let mut generations: Vec<Vec<String>> = Vec::new();
+                for (id, layer) in self.layer_assignment()? {
+                    if generations.len() <= layer {
+                        generations.resize_with(layer + 1, Vec::new);
+                    }
+                    generations[layer].push(id);
+                }
+                for generation in &mut generations {
+                    generation.sort();
+                }
+                Ok(generations)
+            }
+
+            /// Neighbors of each node ignoring edge direction, skipping edges
+            /// to missing nodes.
+            fn undirected_neighbors(&self) -> HashMap<&str, Vec<&str>> {
+                let mut neighbors: HashMap<&str, Vec<&str>> = HashMap::new();
+                for from in self.sorted_ids() {
+                    for to in self.targets(from) {
+                        neighbors.entry(from).or_default().push(to);
+                        neighbors.entry(to).or_default().push(from);
+                    }
+                }
+                neighbors
+            }
+
+            /// A hash of the manifold's nodes, edges and metadata that is
+            /// consistent with `==`: equal manifolds always hash the same,
+            /// whatever their adjacency order.
+            ///
+            /// Stable within one build, but not guaranteed across Rust
+            /// versions, so don't persist it.
+            pub fn structural_hash(&self) -> u64 {
+                fn sorted_pairs(map: &HashMap<String, String>) -> Vec<(&String, &String)> {
+                    let mut pairs: Vec<_> = map.iter().collect();
+                    pairs.sort();
+                    pairs
+                }
+
+                let mut hasher = DefaultHasher::new();
+                for id in self.sorted_ids() {
+                    let node = &self.nodes[id];
+                    (id, &node.data, sorted_pairs(&node.metadata)).hash(&mut hasher);
+                }
+                let mut edges: Vec<_> = self.weighted_edge_counts().into_iter().collect();
+                edges.sort();
+                edges.hash(&mut hasher);
+                sorted_pairs(&self.metadata).hash(&mut hasher);
+                hasher.finish()
+            }
+
+            /// Count each `(from, to, weight)` edge, with the weight as its
+            /// bits, so parallel edges and their weights are preserved.
+            fn weighted_edge_counts(&self) -> HashMap<(&str, &str, u64), usize> {
+                let mut counts = HashMap::new();
+                for (from, targets) in &self.edges {
+                    for edge in targets {
+                        *counts.entry((from.as_str(), edge.to.as_str(), edge.weight.to_bits())).or_insert(0) += 1;
+                    }
+                }
+                counts
+            }
+
+            /// Count each `(from, to)` edge, so parallel edges are preserved.
+            fn edge_counts(&self) -> HashMap<(&str, &str), usize> {
+                let mut counts = HashMap::new();
+                for (from, targets) in &self.edges {
+                    for edge in targets {
+                        *counts.entry((from.as_str(), edge.to.as_str())).or_insert(0) += 1;
+                    }
+                }
+                counts
+            }
+        }
+
+        /// Shape of one weakly-connected component, from [`graph_metrics`].
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct ComponentMetrics {
+            /// The component's node IDs, sorted.
+            pub nodes: Vec<String>,
+            /// Largest eccentricity: the longest shortest path in the component.
+            pub diameter: usize,
+            /// Smallest eccentricity.
+            pub radius: usize,
+        }
+
+        /// Per-component shape metrics of a manifold.
+        #[derive(Debug, Clone, Default, PartialEq, Eq)]
+        pub struct GraphMetrics {
+            /// One entry per weakly-connected component, ordered like
+            /// [`Manifold::weakly_connected_components`].
+            pub components: Vec<ComponentMetrics>,
+        }
+
+        impl GraphMetrics {
+            /// The largest diameter over all components (0 for an empty graph).
+            pub fn diameter(&self) -> usize {
+                self.components.iter().map(|c| c.diameter).max().unwrap_or(0)
+            }
+        }
+
+        /// Diameter and radius of each weakly-connected component, measuring
+        /// hop distance with edges treated as undirected.
+        ///
+        /// Runs a BFS from every node, so it costs O(V·E).
+        pub fn graph_metrics(manifold: &Manifold) -> GraphMetrics {
+            let neighbors = manifold.undirected_neighbors();
+            let eccentricity = |start: &str| {
+                let mut distance: HashMap<&str, usize> = HashMap::from([(start, 0)]);
+                let mut queue = VecDeque::from([start]);
+                let mut farthest = 0;
+                while let Some(id) = queue.pop_front() {
+                    let next = distance[id] + 1;
+                    for &to in neighbors.get(id).into_iter().flatten() {
+                        if !distance.contains_key(to) {
+                            distance.insert(to, next);
+                            farthest = next;
+                            queue.push_back(to);
+                        }
+                    }
+                }
+                farthest
+            };
+
+            let components = manifold
+                .weakly_connected_components()
+                .into_iter()
+                .map(|nodes| {
+                    let eccentricities: Vec<usize> = nodes.iter().map(|id| eccentricity(id)).collect();
+                    ComponentMetrics {
+                        diameter: eccentricities.iter().copied().max().unwrap_or(0),
+                        radius: eccentricities.iter().copied().min().unwrap_or(0),
+                        nodes,
+                    }
+                })
+                .collect();
+            GraphMetrics { components }
+        }
+
+        /// Iterator over `(node, out-neighbors)` pairs in sorted node order,
+        /// created by iterating over `&Manifold`.
+        ///
+        /// Neighbors are listed in adjacency order; edges to missing nodes
+        /// are skipped.
+        pub struct Neighborhoods<'a> {
+            manifold: &'a Manifold,
+            ids: std::vec::IntoIter<&'a str>,
+        }
+
+        impl<'a> Iterator for Neighborhoods<'a> {
+            type Item = (&'a ManifoldNode, Vec<&'a ManifoldNode>);
+
+            fn next(&mut self) -> Option<Self::Item> {
+                let id = self.ids.next()?;
+                let node = &self.manifold.nodes[id];
+                let neighbors = self
+                    .manifold
+                    .targets(id)
+                    .map(|to| &self.manifold.nodes[to])
+                    .collect();
+                Some((node, neighbors))
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.ids.size_hint()
+            }
+        }
+
+        impl<'a> IntoIterator for &'a Manifold {
+            type Item = (&'a ManifoldNode, Vec<&'a ManifoldNode>);
+            type IntoIter = Neighborhoods<'a>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                Neighborhoods {
+                    manifold: self,
+                    ids: self.sorted_ids().into_iter(),
+                }
+            }
+        }
+
+        /// Breadth- or depth-first walk behind [`Manifold::bfs`] and
+        /// [`Manifold::dfs`].
+        struct Traversal<'a> {
+            manifold: &'a Manifold,
+            /// Queue (breadth-first) or stack (depth-first) of nodes to visit.
+            frontier: VecDeque<&'a str>,
+            seen: HashSet<&'a str>,
+            depth_first: bool,
+        }
+
+        impl<'a> Traversal<'a> {
+            fn new(manifold: &'a Manifold, start: &str, depth_first: bool) -> Self {
+                let start = manifold.nodes.get_key_value(start).map(|(id, _)| id.as_str());
+                let mut seen = HashSet::new();
+                if !depth_first {
+                    seen.extend(start);
+                }
+                Traversal {
+                    manifold,
+                    frontier: start.into_iter().collect(),
+                    seen,
+                    depth_first,
+                }
+            }
+        }
+
+        impl<'a> Iterator for Traversal<'a> {
+            type Item = &'a ManifoldNode;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                let manifold = self.manifold;
+                if self.depth_first {
+                    // Nodes are marked when visited, so a node reachable by
+                    // several paths is visited from the first one explored.
+                    loop {
+                        let id = self.frontier.pop_back()?;
+                        if !self.seen.insert(id) {
+                            continue;
+                        }
+                        let targets: Vec<&str> = manifold.targets(id).collect();
+                        self.frontier.extend(targets.into_iter().rev().filter(|t| !self.seen.contains(t)));
+                        return Some(&manifold.nodes[id]);
+                    }
+                }
+                let id = self.frontier.pop_front()?;
+                for to in manifold.targets(id) {
+                    if self.seen.insert(to) {
+                        self.frontier.push_back(to);
+                    }
+                }
+                Some(&manifold.nodes[id])
+            }
+        }
+
+        /// Two manifolds are equal when they hold the same nodes, metadata
+        /// and multiset of weighted edges; adjacency order and empty
+        /// adjacency lists are representation details and don't count.
+        impl PartialEq for Manifold {
+            fn eq(&self, other: &Self) -> bool {
+                self.nodes == other.nodes
+                    && self.metadata == other.metadata
+                    && self.weighted_edge_counts() == other.weighted_edge_counts()
+            }
+        }
+
+        /// Serialized layout of a manifold. Nodes are listed by ID and the
+        /// maps are ordered, so diffs between saved files stay readable.
+        #[derive(Serialize, Deserialize)]
+        struct ManifoldDocument {
+            format_version: u64,
+            nodes: Vec<ManifoldNode>,
+            #[serde(default)]
+            edges: BTreeMap<String, Vec<Edge>>,
+            #[serde(default)]
+            metadata: BTreeMap<String, String>,
+            #[serde(default)]
+            strict_dag: bool,
+            #[serde(default)]
+            simple: bool,
+        }
+
+        /// How [`Manifold::to_json_with`] writes each node's `data`.
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+        pub enum DataEncoding {
+            /// As a JSON string, like [`Manifold::to_json`].
+            #[default]
+            Inline,
+            /// As standard padded base64 of its UTF-8 bytes.
+            Base64,
+            /// As an empty string, keeping only the structure.
+            Omit,
+        }
+
+        /// Options for [`Manifold::to_json_with`].
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+        pub struct SerializeOptions {
+            pub data: DataEncoding,
+        }
+
+        fn base64_encode(bytes: &[u8]) -> String {
+            const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+            let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+            for chunk in bytes.chunks(3) {
+                let group = chunk.iter().enumerate().fold(0u32, |group, (i, &b)| group | (b as u32) << (16 - 8 * i));
+                for i in 0..4 {
+                    if i <= chunk.len() {
+                        encoded.push(ALPHABET[(group >> (18 - 6 * i) & 0x3f) as usize] as char);
+                    } else {
+                        encoded.push('=');
+                    }
+                }
+            }
+            encoded
+        }
+
+        impl Manifold {
+            /// Serialize the manifold to JSON, tagged with
+            /// [`MANIFOLD_FORMAT_VERSION`].
+            pub fn to_json(&self) -> UorResult<String> {
+                self.to_json_with(SerializeOptions::default())
+            }
+
+            /// [`Manifold::to_json`], writing node data as `opts` says.
+            ///
+            /// The output still loads with [`Manifold::from_json`], but the
+            /// nodes then hold the encoded (or empty) data; nothing records
+            /// which encoding was used.
+            pub fn to_json_with(&self, opts: SerializeOptions) -> UorResult<String> {
+                let mut document = self.to_document();
+                for node in &mut document.nodes {
+                    match opts.data {
+                        DataEncoding::Inline => {}
+                        DataEncoding::Base64 => node.data = base64_encode(node.data.as_bytes()),
+                        DataEncoding::Omit => node.data.clear(),
+                    }
+                }
+                serde_json::to_string(&document)
+                    .map_err(|e| UorError::General(format!("Failed to serialize manifold: {}", e)))
+            }
+
+            /// Load a manifold written by [`Manifold::to_json`].
+            ///
+            /// Documents from older format versions are upgraded first; a
+            /// version newer than this build understands is rejected rather
+            /// than guessed at. Edges must connect nodes present in the
+            /// document.
+            pub fn from_json(json: &str) -> UorResult<Manifold> {
+                let value: Value = serde_json::from_str(json)
+                    .map_err(|e| UorError::General(format!("Invalid manifold JSON: {}", e)))?;
+                let version = match value.get("format_version") {
+                    None => 0,
+                    Some(version) => version.as_u64().ok_or_else(|| {
+                        UorError::General(format!("Invalid manifold format_version {}", version))
+                    })?,
+                };
+                let document: ManifoldDocument = serde_json::from_value(upgrade_document(version, value)?)
+                    .map_err(|e| UorError::General(format!("Invalid manifold document: {}", e)))?;
+                Manifold::from_document(document)
+            }
+
+            /// Serialize the manifold to a compact binary form (bincode with
+            /// variable-length integers), for large graphs where JSON is too
+            /// verbose.
+            #[cfg(feature = "bincode")]
+            pub fn to_bincode(&self) -> UorResult<Vec<u8>> {
+                use bincode::Options;
+                bincode::DefaultOptions::new()
+                    .serialize(&self.to_document())
+                    .map_err(|e| UorError::General(format!("Failed to serialize manifold: {}", e)))
+            }
+
+            /// Load a manifold written by [`Manifold::to_bincode`].
+            ///
+            /// The binary form isn't self-describing, so unlike
+            /// [`Manifold::from_json`] there is no upgrade path: only the
+            /// current format version is accepted. Edges must connect nodes
+            /// present in the document.
+            #[cfg(feature = "bincode")]
+            pub fn from_bincode(bytes: &[u8]) -> UorResult<Manifold> {
+                use bincode::Options;
+                let document: ManifoldDocument = bincode::DefaultOptions::new()
+                    .deserialize(bytes)
+                    .map_err(|e| UorError::General(format!("Invalid manifold bincode: {}", e)))?;
+                Manifold::from_current_document(document)
+            }
+
+            /// Convert to a petgraph `DiGraph` whose node weights are the
+            /// nodes' data, plus the index of each node ID.
+            ///
+            /// Nodes are inserted in ID order; parallel edges are kept and
+            /// edges to missing nodes dropped. Metadata and weights are not
+            /// carried over.
+            #[cfg(feature = "petgraph")]
+            pub fn to_petgraph(&self) -> (DiGraph<String, ()>, HashMap<String, NodeIndex>) {
+                let mut graph = DiGraph::with_capacity(self.nodes.len(), 0);
+                let mut indices = HashMap::with_capacity(self.nodes.len());
+                for id in self.sorted_ids() {
+                    indices.insert(id.to_string(), graph.add_node(self.nodes[id].data.clone()));
+                }
+                for from in self.sorted_ids() {
+                    for to in self.targets(from) {
+                        graph.add_edge(indices[from], indices[to], ());
+                    }
+                }
+                (graph, indices)
+            }
+
+            /// Build a manifold from a petgraph `DiGraph`, naming each node by
+            /// `ids` (as returned from [`Manifold::to_petgraph`]) and using its
+            /// weight as the node's data.
+            ///
+            /// Errors unless `ids` names every node of `graph` exactly once.
+            #[cfg(feature = "petgraph")]
+            pub fn from_petgraph(graph: &DiGraph<String, ()>, ids: &HashMap<String, NodeIndex>) -> UorResult<Manifold> {
+                let mut names: Vec<Option<&str>> = vec![None; graph.node_count()];
+                for (id, index) in ids {
+                    let slot = names.get_mut(index.index()).ok_or_else(|| {
+                        UorError::General(format!("Node {} refers to missing graph index {}", id, index.index()))
+                    })?;
+                    if let Some(other) = slot.replace(id) {
+                        return Err(UorError::General(format!(
+                            "Nodes {} and {} share graph index {}",
+                            other,
+                            id,
+                            index.index()
+                        )));
+                    }
+                }
+
+                let mut manifold = Manifold::new();
+                for index in graph.node_indices() {
+                    let id = names[index.index()].ok_or_else(|| {
+                        UorError::General(format!("Graph index {} has no node ID", index.index()))
+                    })?;
+                    manifold.add_node(ManifoldNode::new(id, &graph[index]));
+                }
+                for edge in graph.raw_edges() {
+                    let (from, to) = (names[edge.source().index()], names[edge.target().index()]);
+                    manifold.add_edge(from.expect("every index is named"), to.expect("every index is named"))?;
+                }
+                Ok(manifold)
+            }
+
+            fn to_document(&self) -> ManifoldDocument {
+                ManifoldDocument {
+                    format_version: MANIFOLD_FORMAT_VERSION,
+                    nodes: self.sorted_ids().into_iter().map(|id| self.nodes[id].clone()).collect(),
+                    edges: self
+                        .edges
+                        .iter()
+                        .filter(|(_, targets)| !targets.is_empty())
+                        .map(|(from, targets)| (from.clone(), targets.clone()))
+                        .collect(),
+                    metadata: self.metadata.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+                    strict_dag: self.strict_dag,
+                    simple: self.simple,
+                }
+            }
+
+            /// [`Manifold::from_document`] for formats with no upgrade path,
+            /// which only accept the current format version.
+            fn from_current_document(document: ManifoldDocument) -> UorResult<Manifold> {
+                if document.format_version != MANIFOLD_FORMAT_VERSION {
+                    return Err(UorError::General(format!(
+                        "Unsupported manifold format_version {} (only version {} can be read without upgrading)",
+                        document.format_version, MANIFOLD_FORMAT_VERSION
+                    )));
+                }
+                Manifold::from_document(document)
+            }
+
+            fn from_document(document: ManifoldDocument) -> UorResult<Manifold> {
+                let mut manifold = Manifold::new();
+                for node in document.nodes {
+                    manifold.add_node(node);
+                }
+                for (from, targets) in &document.edges {
+                    for edge in targets {
+                        manifold.add_weighted_edge(from, &edge.to, edge.weight)?;
+                    }
+                }
+                manifold.metadata = document.metadata.into_iter().collect();
+                // Set after the edges, which are restored exactly as saved.
+                manifold.strict_dag = document.strict_dag;
+                manifold.simple = document.simple;
+                Ok(manifold)
+            }
+        }
+
+        /// Serializes in the same layout as [`Manifold::to_json`], so a
+        /// manifold can be embedded in other serde types.
+        impl Serialize for Manifold {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                self.to_document().serialize(serializer)
+            }
+        }
+
+        /// Only the current [`MANIFOLD_FORMAT_VERSION`] is accepted, since not
+        /// every serde format can be upgraded; use [`Manifold::from_json`] for
+        /// older JSON. Edges must connect nodes present in the document.
+        impl<'de> Deserialize<'de> for Manifold {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let document = ManifoldDocument::deserialize(deserializer)?;
+                Manifold::from_current_document(document).map_err(|e| match e {
+                    UorError::General(message) => serde::de::Error::custom(message),
+                    other => serde::de::Error::custom(format!("{:?}", other)),
+                })
+            }
+        }
+
+        /// Rewrite a serialized manifold written at `version` into the current
+        /// layout. Each older version gets an arm here when the layout changes.
+        fn upgrade_document(version: u64, mut value: Value) -> UorResult<Value> {
+            match version {
+                MANIFOLD_FORMAT_VERSION => Ok(value),
+                // Written before the format was versioned; same layout as 1.
+                0 => {
+                    document_object(&mut value)?.insert("format_version".into(), 1.into());
+                    upgrade_document(1, value)
+                }
+                // Edges were bare target IDs, with weights kept apart in an
+                // `edge_weights` map by `from`, then `to`.
+                1 => {
+                    let object = document_object(&mut value)?;
+                    let weights = object.remove("edge_weights").unwrap_or_default();
+                    for (from, targets) in object.get_mut("edges").and_then(Value::as_object_mut).into_iter().flatten() {
+                        for target in targets.as_array_mut().into_iter().flatten() {
+                            let Some(to) = target.as_str() else {
+                                continue;
+                            };
+                            let weight = weights[from.as_str()][to].as_f64().unwrap_or(1.0);
+                            *target = json!({ "to": to, "weight": weight });
+                        }
+                    }
+                    object.insert("format_version".into(), 2.into());
+                    upgrade_document(2, value)
+                }
+                // Had no `strict_dag` or `simple` flags; both default to off.
+                2 => {
+                    document_object(&mut value)?.insert("format_version".into(), 3.into());
+                    Ok(value)
+                }
+                _ => Err(UorError::General(format!(
+                    "Unsupported manifold format_version {} (this build reads up to {})",
+                    version, MANIFOLD_FORMAT_VERSION
+                ))),
+            }
+        }
+
+        fn document_object(value: &mut Value) -> UorResult<&mut serde_json::Map<String, Value>> {
+            value
+                .as_object_mut()
+                .ok_or_else(|| UorError::General("Manifold document must be a JSON object".into()))
+        }
+
+        /// A recorded edit, with whatever is needed to reverse it.
+        #[derive(Debug, Clone)]
+        enum Edit {
+            AddNode { node: ManifoldNode, replaced: Option<ManifoldNode> },
+            RemoveNode {
+                node: ManifoldNode,
+                outgoing: Vec<Edge>,
+                /// `(from, position, weight)` of every edge into the node, in
+                /// the order they must be re-inserted.
+                incoming: Vec<(String, usize, f64)>,
+            },
+            AddEdge { from: String, to: String },
+            RemoveEdge { from: String, edge: Edge, position: usize },
+        }
+
+        /// A manifold wrapper that records each mutation so it can be undone
+        /// and redone, for editor-style workflows.
+        ///
+        /// Making a new edit after an undo discards the redo history.
+        #[derive(Debug, Clone, Default)]
+        pub struct EditableManifold {
+            manifold: Manifold,
+            undo_stack: Vec<Edit>,
+            redo_stack: Vec<Edit>,
+        }
+
+        impl EditableManifold {
+            pub fn new(manifold: Manifold) -> Self {
+                Self {
+                    manifold,
+                    undo_stack: Vec::new(),
+                    redo_stack: Vec::new(),
+                }
+            }
+
+            /// The current state of the manifold.
+            pub fn manifold(&self) -> &Manifold {
+                &self.manifold
+            }
+
+            /// Stop editing and take the manifold, dropping the history.
+            pub fn into_inner(self) -> Manifold {
+                self.manifold
+            }
+
+            /// Add (or replace) a node.
+            pub fn add_node(&mut self, node: ManifoldNode) {
+                let replaced = self.manifold.nodes.get(&node.id).cloned();
+                self.manifold.add_node(node.clone());
+                self.record(Edit::AddNode { node, replaced });
+            }
+
+            /// Remove a node together with every edge touching it.
+            pub fn remove_node(&mut self, id: &str) -> UorResult<()> {
+                let node = self
+                    .manifold
+                    .nodes
+                    .remove(id)
+                    .ok_or_else(|| UorError::General(format!("Node {} not found", id)))?;
+                let outgoing = self.manifold.edges.remove(id).unwrap_or_default();
+
+                let mut incoming = Vec::new();
+                for (from, targets) in self.manifold.edges.iter_mut() {
+                    // Positions are recorded in the original list, so
+                    // re-inserting them in ascending order restores it.
+                    let mut position = 0;
+                    targets.retain(|edge| {
+                        let keep = edge.to != id;
+                        if !keep {
+                            incoming.push((from.clone(), position, edge.weight));
+                        }
+                        position += 1;
+                        keep
+                    });
+                }
+                self.manifold.edges.retain(|_, targets| !targets.is_empty());
+                incoming.sort_by(|a, b| (&a.0, a.1).cmp(&(&b.0, b.1)));
+
+                self.record(Edit::RemoveNode { node, outgoing, incoming });
+                Ok(())
+            }
+
+            /// Add a directed edge between two existing nodes.
+            ///
+            /// On a `simple` manifold that already has the edge nothing
+            /// changes, so nothing is recorded.
+            pub fn add_edge(&mut self, from: &str, to: &str) -> UorResult<()> {
+                let before = self.manifold.edges.get(from).map_or(0, Vec::len);
+                self.manifold.add_edge(from, to)?;
+                if self.manifold.edges[from].len() > before {
+                    self.record(Edit::AddEdge { from: from.into(), to: to.into() });
+                }
+                Ok(())
+            }
+
+            /// Remove one `from -> to` edge.
+            pub fn remove_edge(&mut self, from: &str, to: &str) -> UorResult<()> {
+                let position = self
+                    .manifold
+                    .edges
+                    .get(from)
+                    .and_then(|targets| targets.iter().position(|edge| edge.to == to))
+                    .ok_or_else(|| {
+                        UorError::General(format!("No edge from {} to {}", from, to))
+                    })?;
+                let edge = Self::take_edge(&mut self.manifold, from, position);
+                self.record(Edit::RemoveEdge { from: from.into(), edge, position });
+                Ok(())
+            }
+
+            /// Reverse the most recent edit. Returns `false` if there was
+            /// nothing to undo.
+            pub fn undo(&mut self) -> bool {
+                let Some(edit) = self.undo_stack.pop() else {
+                    return false;
+                };
+                let manifold = &mut self.manifold;
+                match &edit {
+                    Edit::AddNode { node, replaced } => match replaced {
+                        Some(previous) => manifold.add_node(previous.clone()),
+                        None => {
+                            manifold.nodes.remove(&node.id);
+                        }
+                    },
+                    Edit::RemoveNode { node, outgoing, incoming } => {
+                        manifold.add_node(node.clone());
+                        if !outgoing.is_empty() {
+                            manifold.edges.insert(node.id.clone(), outgoing.clone());
+                        }
+                        for (from, position, weight) in incoming {
+                            let targets = manifold.edges.entry(from.clone()).or_default();
+                            targets.insert(*position, Edge::weighted(&node.id, *weight));
+                        }
+                    }
+                    Edit::AddEdge { from, to } => {
+                        let position = manifold.edges[from]
+                            .iter()
+                            .rposition(|edge| edge.to == *to)
+                            .expect("an added edge is present until undone");
+                        Self::take_edge(manifold, from, position);
+                    }
+                    Edit::RemoveEdge { from, edge, position } => {
+                        let targets = manifold.edges.entry(from.clone()).or_default();
+                        targets.insert(*position, edge.clone());
+                    }
+                }
+                self.redo_stack.push(edit);
+                true
+            }
+
+            /// Re-apply the most recently undone edit. Returns `Ok(false)` if
+            /// there was nothing to redo.
+            ///
+            /// If the edit can no longer be applied, the error is returned
+            /// and the edit stays on the redo stack.
+            pub fn redo(&mut self) -> UorResult<bool> {
+                let Some(edit) = self.redo_stack.pop() else {
+                    return Ok(false);
+                };
+                let mut redo_stack = std::mem::take(&mut self.redo_stack);
+                let result = match edit.clone() {
+                    Edit::AddNode { node, .. } => {
+                        self.add_node(node);
+                        Ok(())
+                    }
+                    Edit::RemoveNode { node, .. } => self.remove_node(&node.id),
+                    Edit::AddEdge { from, to } => self.add_edge(&from, &to),
+                    Edit::RemoveEdge { from, edge, .. } => self.remove_edge(&from, &edge.to),
+                };
+                if result.is_err() {
+                    redo_stack.push(edit);
+                }
+                self.redo_stack = redo_stack;
+                result.map(|()| true)
+            }
+
+            fn record(&mut self, edit: Edit) {
+                self.undo_stack.push(edit);
+                self.redo_stack.clear();
+            }
+
+            fn take_edge(manifold: &mut Manifold, from: &str, position: usize) -> Edge {
+                let targets = manifold.edges.get_mut(from).expect("edge source has targets");
+                let edge = targets.remove(position);
+                if targets.is_empty() {
+                    manifold.edges.remove(from);
+                }
+                edge
+            }
+        }
+    }
+
+    // 2.3. foundation_model
+    //      Describes the “abstract class” or trait for a UOR Foundation Model.
+    //      This model can be specialized (e.g., for spinor geometry, or for
+    //      language parsing). Each “slot” in the stack references an instance
+    //      of this trait.
+
+    /// The foundation_model module provides traits and definitions for how
+    /// various domain-specific or multi-modal “foundation models” interact
+    /// with manifold data.
+    pub mod foundation_model {
+        use super::manifold::Manifold;
+        use super::{UorResult, UorError};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::mpsc::{self, RecvTimeoutError};
+        use std::sync::{Arc, Mutex, TryLockError};
+        use std::thread;
+        use std::time::Duration;
+
+        /// Trait that all foundation models must implement.
+        ///
+        /// A FoundationModel can transform manifolds, interpret them,
+        /// or produce new manifolds as output. This is the “abstract class”
+        /// in the UOR design.
+        pub trait FoundationModel {
+            /// Process an input manifold and return a transformed or
+            /// enriched manifold.
+            fn process_manifold(&mut self, input: &Manifold) -> UorResult<Manifold>;
+
+            /// Human-readable name used when describing a stack. Defaults to
+            /// the implementing type's name.
+            fn name(&self) -> &str {
+                std::any::type_name::<Self>()
+            }
+
+            /// A boxed copy of this model, or `None` (the default) if it
+            /// can't be copied. Lets [`CognitiveStack::try_clone`] duplicate
+            /// stacks of boxed models.
+            ///
+            /// [`CognitiveStack::try_clone`]: super::cognitive_stack::CognitiveStack::try_clone
+            fn clone_box(&self) -> Option<Box<dyn FoundationModel>> {
+                None
+            }
+
+            /// Usage counters for profiling, if the model keeps any. The
+            /// default reports nothing.
+            fn metrics(&self) -> Option<ModelMetrics> {
+                None
+            }
+
+            /// Reseed any randomness the model uses, for reproducible runs.
+            /// The default does nothing.
+            fn set_seed(&mut self, _seed: u64) {}
+
+            /// Check that `input` is something the model can process (e.g.
+            /// acyclic), so a stack can fail cleanly instead of running the
+            /// model on it. The default accepts everything.
+            fn precondition(&self, _input: &Manifold) -> UorResult<()> {
+                Ok(())
+            }
+
+            /// Do any expensive one-off setup (e.g. loading weights) ahead
+            /// of the first call. The default does nothing.
+            fn warmup(&mut self) -> UorResult<()> {
+                Ok(())
+            }
+        }
+
+        /// A model whose processing is asynchronous, e.g. one that awaits a
+        /// remote inference server. Requires the `async` feature.
+        ///
+        /// Every [`FoundationModel`] is also an `AsyncFoundationModel` whose
+        /// future completes immediately. This trait is not re-exported from
+        /// the crate root, so that `process_manifold` calls stay unambiguous
+        /// for code that imports everything from there.
+        #[cfg(feature = "async")]
+        #[allow(async_fn_in_trait)]
+        pub trait AsyncFoundationModel {
+            async fn process_manifold(&mut self, input: &Manifold) -> UorResult<Manifold>;
+
+            /// Like [`FoundationModel::precondition`]. The default accepts
+            /// everything.
+            fn precondition(&self, _input: &Manifold) -> UorResult<()> {
+                Ok(())
+            }
+        }
+
+        #[cfg(feature = "async")]
+        impl<M: FoundationModel> AsyncFoundationModel for M {
+            async fn process_manifold(&mut self, input: &Manifold) -> UorResult<Manifold> {
+                FoundationModel::process_manifold(self, input)
+            }
+
+            fn precondition(&self, input: &Manifold) -> UorResult<()> {
+                FoundationModel::precondition(self, input)
+            }
+        }
+
+        /// What a model has processed so far, from
+        /// [`FoundationModel::metrics`].
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+        pub struct ModelMetrics {
+            pub manifolds_processed: usize,
+            pub nodes_processed: usize,
+        }
+
+        /// Models a [`CognitiveStack`](super::cognitive_stack::CognitiveStack)
+        /// knows how to copy: any `Clone` model, and boxed models whose
+        /// [`FoundationModel::clone_box`] returns a copy.
+        pub trait TryCloneModel: Sized {
+            fn try_clone_model(&self) -> Option<Self>;
+        }
+
+        impl<M: FoundationModel + Clone> TryCloneModel for M {
+            fn try_clone_model(&self) -> Option<Self> {
+                Some(self.clone())
+            }
+        }
+
+        impl TryCloneModel for Box<dyn FoundationModel> {
+            fn try_clone_model(&self) -> Option<Self> {
+                self.as_ref().clone_box()
+            }
+        }
+
+        // Optional: provide a default foundation model as a stub
+        // that does nothing. This allows easy testing of "M: Default"
+        // if needed.
+        #[derive(Clone, Default)]
+        pub struct NullFoundationModel;
+
+        /// Boxed models are models too, so a stack can hold a mix of
+        /// concrete model types as `Vec<Box<dyn FoundationModel>>`.
+        impl<F: FoundationModel + ?Sized> FoundationModel for Box<F> {
+            fn process_manifold(&mut self, input: &Manifold) -> UorResult<Manifold> {
+                (**self).process_manifold(input)
+            }
+
+            fn name(&self) -> &str {
+                (**self).name()
+            }
+
+            fn clone_box(&self) -> Option<Box<dyn FoundationModel>> {
+                (**self).clone_box()
+            }
+
+            fn metrics(&self) -> Option<ModelMetrics> {
+                (**self).metrics()
+            }
+
+            fn set_seed(&mut self, seed: u64) {
+                (**self).set_seed(seed)
+            }
+
+            fn precondition(&self, input: &Manifold) -> UorResult<()> {
+                (**self).precondition(input)
+            }
+
+            fn warmup(&mut self) -> UorResult<()> {
+                (**self).warmup()
+            }
+        }
+
+        impl FoundationModel for NullFoundationModel {
+            fn process_manifold(&mut self, input: &Manifold) -> UorResult<Manifold> {
+                Ok(input.clone())
+            }
+
+            fn clone_box(&self) -> Option<Box<dyn FoundationModel>> {
+                Some(Box::new(self.clone()))
+            }
+        }
+
+        /// Wraps a model so a call that overruns `deadline` fails with
+        /// `UorError::ConcurrencyError` instead of stalling the pipeline.
+        ///
+        /// The inner model runs on a worker thread rather than a scoped one,
+        /// because a scope must join its threads before returning and so
+        /// could never give up on a model that hangs. A model that overruns
+        /// keeps running in the background; further calls fail until it
+        /// finishes. No method blocks waiting for it.
+        pub struct TimeBoundedModel<M> {
+            model: Arc<Mutex<M>>,
+            pub deadline: Duration,
+            /// A seed that arrived while the model was busy, applied the next
+            /// time it is free.
+            pending_seed: Option<u64>,
+        }
+
+        impl<M> TimeBoundedModel<M> {
+            pub fn new(model: M, deadline: Duration) -> Self {
+                Self {
+                    model: Arc::new(Mutex::new(model)),
+                    deadline,
+                    pending_seed: None,
+                }
+            }
+        }
+
+        impl<M: FoundationModel> TimeBoundedModel<M> {
+            /// Lock the inner model without waiting, applying any pending
+            /// seed first.
+            fn try_model(&mut self) -> UorResult<std::sync::MutexGuard<'_, M>> {
+                let mut model = match self.model.try_lock() {
+                    Ok(model) => model,
+                    Err(TryLockError::WouldBlock) => {
+                        return Err(UorError::ConcurrencyError(
+                            "Model is still running a previous call".into(),
+                        ))
+                    }
+                    Err(TryLockError::Poisoned(_)) => {
+                        return Err(UorError::ConcurrencyError(
+                            "Model panicked during a previous call".into(),
+                        ))
+                    }
+                };
+                if let Some(seed) = self.pending_seed.take() {
+                    model.set_seed(seed);
+                }
+                Ok(model)
+            }
+        }
+
+        impl<M> FoundationModel for TimeBoundedModel<M>
+        where
+            M: FoundationModel + Send + 'static,
+        {
+            fn process_manifold(&mut self, input: &Manifold) -> UorResult<Manifold> {
+                if self.pending_seed.is_some() {
+                    // Apply the seed now if the model is free; if not, the
+                    // worker below fails the call anyway.
+                    drop(self.try_model());
+                }
+                let (sender, receiver) = mpsc::channel();
+                let model = Arc::clone(&self.model);
+                let input = input.clone();
+
+                thread::spawn(move || {
+                    let result = match model.try_lock() {
+                        Ok(mut model) => model.process_manifold(&input),
+                        Err(TryLockError::WouldBlock) => Err(UorError::ConcurrencyError(
+                            "Model is still running a previous call".into(),
+                        )),
+                        Err(TryLockError::Poisoned(_)) => Err(UorError::ConcurrencyError(
+                            "Model panicked during a previous call".into(),
+                        )),
+                    };
+                    // The caller may have stopped waiting; that's fine.
+                    let _ = sender.send(result);
+                });
+
+                match receiver.recv_timeout(self.deadline) {
+                    Ok(result) => result,
+                    Err(RecvTimeoutError::Timeout) => Err(UorError::ConcurrencyError(format!(
+                        "Model exceeded its deadline of {:?}",
+                        self.deadline
+                    ))),
+                    Err(RecvTimeoutError::Disconnected) => Err(UorError::ConcurrencyError(
+                        "Model panicked while processing".into(),
+                    )),
+                }
+            }
+
+            /// The inner model's metrics, or `None` while a call that
+            /// overran its deadline is still holding the model.
+            fn metrics(&self) -> Option<ModelMetrics> {
+                self.model.try_lock().ok()?.metrics()
+            }
+
+            /// Reseeds the inner model. While a call that overran its
+            /// deadline still holds the model, the seed is kept and applied
+            /// on the next call (or `warmup`) that finds the model free.
+            fn set_seed(&mut self, seed: u64) {
+                self.pending_seed = Some(seed);
+                drop(self.try_model());
+            }
+
+            /// Checks the inner model's precondition, unless a call that
+            /// overran its deadline still holds the model (the call then
+            /// fails anyway).
+            fn precondition(&self, input: &Manifold) -> UorResult<()> {
+                match self.model.try_lock() {
+                    Ok(model) => model.precondition(input),
+                    Err(_) => Ok(()),
+                }
+            }
+
+            /// Warms up the inner model without a deadline. Fails with
+            /// `UorError::ConcurrencyError` while a call that overran its
+            /// deadline still holds the model.
+            fn warmup(&mut self) -> UorResult<()> {
+                self.try_model()?.warmup()
+            }
+        }
+
+        /// Wraps a model and counts its `process_manifold` calls, so tests
+        /// can check that a stack actually ran it. A counter built with
+        /// [`CountingFoundationModel::new`] wraps [`NullFoundationModel`] and
+        /// passes manifolds through unchanged.
+        ///
+        /// The count is shared: keep a clone of `call_count` (or of the
+        /// whole wrapper) before moving the model into a stack. Copies made
+        /// with `clone_box` share it too, and `metrics` reports it as
+        /// `manifolds_processed`.
+        #[derive(Clone, Default)]
+        pub struct CountingFoundationModel<M = NullFoundationModel> {
+            pub inner: M,
+            /// Calls so far, including ones that failed.
+            pub call_count: Arc<AtomicUsize>,
+        }
+
+        impl CountingFoundationModel {
+            pub fn new() -> Self {
+                Self::default()
+            }
+        }
+
+        impl<M> CountingFoundationModel<M> {
+            pub fn wrap(inner: M) -> Self {
+                Self { inner, call_count: Arc::new(AtomicUsize::new(0)) }
+            }
+
+            /// The current value of `call_count`.
+            pub fn calls(&self) -> usize {
+                self.call_count.load(Ordering::SeqCst)
+            }
+        }
+
+        impl<M: FoundationModel> FoundationModel for CountingFoundationModel<M> {
+            fn process_manifold(&mut self, input: &Manifold) -> UorResult<Manifold> {
+                self.call_count.fetch_add(1, Ordering::SeqCst);
+                self.inner.process_manifold(input)
+            }
+
+            fn name(&self) -> &str {
+                self.inner.name()
+            }
+
+            fn clone_box(&self) -> Option<Box<dyn FoundationModel>> {
+                let inner = self.inner.clone_box()?;
+                Some(Box::new(CountingFoundationModel { inner, call_count: Arc::clone(&self.call_count) }))
+            }
+
+            fn metrics(&self) -> Option<ModelMetrics> {
+                Some(ModelMetrics {
+                    manifolds_processed: self.calls(),
+                    ..self.inner.metrics().unwrap_or_default()
+                })
+            }
+
+            fn set_seed(&mut self, seed: u64) {
+                self.inner.set_seed(seed)
+            }
+
+            fn precondition(&self, input: &Manifold) -> UorResult<()> {
+                self.inner.precondition(input)
+            }
+
+            fn warmup(&mut self) -> UorResult<()> {
+                self.inner.warmup()
+            }
+        }
+    }
+
+    // 2.4. cortex
+    //      Manages the “144 sequential prime reference points” memory space
+    //      for each manifold, storing relevant embeddings or partial expansions.
+
+    /// The cortex module manages memory-specific data structures for the UOR
+    /// framework, including the “144 prime reference points” used for storing
+    /// manifold embeddings, partial expansions, etc.
+    pub mod cortex {
+        use super::manifold::Manifold;
+        use super::{UorResult, UorError};
+        use serde::{Deserialize, Serialize};
+        use std::collections::VecDeque;
+
+        /// A small placeholder for the prime-based memory storage.
+        /// In a real implementation, each “reference point” could hold
+        /// numeric expansions, partial sums, concurrency tasks, etc.
+        #[derive(Debug, Clone, Serialize, Deserialize)]
+        pub struct PrimeReference {
+            pub prime_index: usize,
+            /// The prime this slot is anchored to.
+            pub prime_value: u64,
+            pub data: Option<f64>, // or more sophisticated field
+            /// Optional label describing what this slot holds.
+            pub tag: Option<String>,
+        }
+
+        /// The UOR “cortex” which holds the memory space for a single manifold.
+        #[derive(Debug, Clone)]
+        pub struct MemoryCortex {
+            pub references: Vec<PrimeReference>,
+            /// The prime of each slot, in slot order.
+            primes: Vec<u64>,
+        }
+
+        /// Serializes the slots, tags included, as `{"references": [...]}`.
+        impl Serialize for MemoryCortex {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                use serde::ser::SerializeStruct;
+                let mut state = serializer.serialize_struct("MemoryCortex", 1)?;
+                state.serialize_field("references", &self.references)?;
+                state.end()
+            }
+        }
+
+        /// Slots must be listed in order, each `prime_index` matching its
+        /// position, with primes that [`MemoryCortex::new_with_primes`]
+        /// accepts.
+        impl<'de> Deserialize<'de> for MemoryCortex {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                #[derive(Deserialize)]
+                struct CortexDocument {
+                    references: Vec<PrimeReference>,
+                }
+
+                let document = CortexDocument::deserialize(deserializer)?;
+                if let Some((i, slot)) = document.references.iter().enumerate().find(|(i, slot)| slot.prime_index != *i) {
+                    return Err(serde::de::Error::custom(format!(
+                        "Cortex slot {} has prime_index {}",
+                        i, slot.prime_index
+                    )));
+                }
+                let primes: Vec<u64> = document.references.iter().map(|slot| slot.prime_value).collect();
+                let mut cortex = MemoryCortex::new_with_primes(&primes).map_err(|e| match e {
+                    UorError::General(message) => serde::de::Error::custom(message),
+                    other => serde::de::Error::custom(format!("{:?}", other)),
+                })?;
+                cortex.references = document.references;
+                Ok(cortex)
+            }
+        }
+
+        // Provide a Default implementation so it can be used in default
+        // constructors of other structures.
+        impl Default for MemoryCortex {
+            fn default() -> Self {
+                Self::new_144()
+            }
+        }
 
         impl MemoryCortex {
-            /// Initialize the MemoryCortex with 144 prime references.
+            /// Initialize the MemoryCortex with 144 prime references, slot
+            /// `i` anchored to the `i`-th prime (2, 3, 5, ..., 827).
             pub fn new_144() -> Self {
-                // Potentially fetch the first 144 primes for prime_index
-                // For now, just store placeholders
-                let refs: Vec<PrimeReference> = (0..144)
-                    .map(|i| PrimeReference {
+                Self::with_capacity(144).expect("144 is a valid capacity")
+            }
+
+            /// A cortex of `n` slots anchored to the first `n` primes.
+            /// Errors if `n` is zero.
+            pub fn with_capacity(n: usize) -> UorResult<Self> {
+                Self::new_with_primes(&first_primes(n))
+            }
+
+            /// Append `additional` empty slots anchored to the primes after
+            /// the last slot's. Existing slots, data and tags are untouched.
+            pub fn grow(&mut self, additional: usize) {
+                let start = self.references.len();
+                let last = self.primes.last().copied().unwrap_or(1);
+                // A standard table continues within the sieve; a custom one
+                // may end far past it, so carry on by testing each number.
+                let mut extra: Vec<u64> = first_primes(start + additional)
+                    .into_iter()
+                    .filter(|&p| p > last)
+                    .take(additional)
+                    .collect();
+                let mut candidate = extra.last().copied().unwrap_or(last);
+                while extra.len() < additional {
+                    candidate = candidate.checked_add(1).expect("no primes left below u64::MAX");
+                    if is_prime(candidate) {
+                        extra.push(candidate);
+                    }
+                }
+                for (i, &prime) in extra.iter().enumerate() {
+                    self.references.push(PrimeReference {
+                        prime_index: start + i,
+                        prime_value: prime,
+                        data: None,
+                        tag: None,
+                    });
+                    self.primes.push(prime);
+                }
+            }
+
+            /// Build a cortex with one slot per caller-supplied prime, for
+            /// callers with a precomputed or custom prime table.
+            ///
+            /// The values are not checked for primality, only that there is
+            /// at least one and that they are positive and strictly
+            /// increasing.
+            pub fn new_with_primes(primes: &[u64]) -> UorResult<Self> {
+                if primes.is_empty() {
+                    return Err(UorError::General("A cortex needs at least one slot".into()));
+                }
+                if primes.first() == Some(&0) {
+                    return Err(UorError::General("Prime values must be positive".into()));
+                }
+                if let Some(pair) = primes.windows(2).find(|pair| pair[0] >= pair[1]) {
+                    return Err(UorError::General(format!(
+                        "Prime values must be strictly increasing, but {} is followed by {}",
+                        pair[0], pair[1]
+                    )));
+                }
+                let references = primes
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &prime)| PrimeReference {
                         prime_index: i,
-                        data: None
+                        prime_value: prime,
+                        data: None,
+                        tag: None,
+                    })
+                    .collect();
+                Ok(Self {
+                    references,
+                    primes: primes.to_vec(),
+                })
+            }
+
+            /// The prime of each slot, in slot order.
+            pub fn primes(&self) -> &[u64] {
+                &self.primes
+            }
+
+            /// Label the slot at `index`, replacing any previous tag.
+            pub fn tag_slot(&mut self, index: usize, tag: &str) -> UorResult<()> {
+                self.slot_mut(index)?.tag = Some(tag.into());
+                Ok(())
+            }
+
+            /// Indices of the slots carrying `tag`, in ascending order.
+            pub fn find_by_tag(&self, tag: &str) -> Vec<usize> {
+                self.references
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, r)| r.tag.as_deref() == Some(tag))
+                    .map(|(i, _)| i)
+                    .collect()
+            }
+
+            /// Store `value` in the slot at `prime_index`.
+            ///
+            /// Errors if the index is past the last slot (143 for
+            /// [`MemoryCortex::new_144`]).
+            pub fn set(&mut self, prime_index: usize, value: f64) -> UorResult<()> {
+                self.slot_mut(prime_index)?.data = Some(value);
+                Ok(())
+            }
+
+            /// The data in the slot at `prime_index`, or `None` if the slot is
+            /// empty or out of range.
+            pub fn get(&self, prime_index: usize) -> Option<f64> {
+                self.references.get(prime_index)?.data
+            }
+
+            /// Number of slots holding data; the same as
+            /// [`MemoryCortex::filled_count`].
+            pub fn occupied(&self) -> usize {
+                self.filled_count()
+            }
+
+            /// Slots whose data differs from `other`'s, as
+            /// `(index, self's data, other's data)` in index order.
+            ///
+            /// Slots that only one cortex has count as empty in the other.
+            pub fn diff(&self, other: &MemoryCortex) -> Vec<(usize, Option<f64>, Option<f64>)> {
+                (0..self.references.len().max(other.references.len()))
+                    .map(|index| (index, self.get(index), other.get(index)))
+                    .filter(|(_, old, new)| old != new)
+                    .collect()
+            }
+
+            /// Empty every slot's data. Tags are kept.
+            pub fn clear(&mut self) {
+                for reference in &mut self.references {
+                    reference.data = None;
+                }
+            }
+
+            /// Iterate over the data values of the filled references.
+            fn filled_values(&self) -> impl Iterator<Item = f64> + '_ {
+                self.references.iter().filter_map(|r| r.data)
+            }
+
+            /// Number of references currently holding data.
+            pub fn filled_count(&self) -> usize {
+                self.filled_values().count()
+            }
+
+            /// Sum of all filled references' data (0.0 when nothing is filled).
+            pub fn sum(&self) -> f64 {
+                self.filled_values().sum()
+            }
+
+            /// Mean of the filled references' data, or `None` if none are filled.
+            pub fn mean(&self) -> Option<f64> {
+                let count = self.filled_count();
+                if count == 0 {
+                    return None;
+                }
+                Some(self.sum() / count as f64)
+            }
+
+            /// Largest filled data value, or `None` if none are filled.
+            pub fn max(&self) -> Option<f64> {
+                self.filled_values().reduce(f64::max)
+            }
+
+            /// Smallest filled data value, or `None` if none are filled.
+            pub fn min(&self) -> Option<f64> {
+                self.filled_values().reduce(f64::min)
+            }
+
+            /// Link the manifold’s data to the prime references in some way.
+            pub fn link_manifold(&mut self, _manifold: &Manifold) -> UorResult<()> {
+                // Implementation is domain-specific
+                Ok(())
+            }
+
+            fn slot_mut(&mut self, index: usize) -> UorResult<&mut PrimeReference> {
+                let slots = self.references.len();
+                self.references.get_mut(index).ok_or_else(|| {
+                    UorError::General(format!(
+                        "Slot {} is out of range for a cortex of {} slots",
+                        index, slots
+                    ))
+                })
+            }
+        }
+
+        /// The first `n` primes, by a sieve of Eratosthenes.
+        ///
+        /// The sieve bound is Rosser's `n (ln n + ln ln n)`, which is at
+        /// least the `n`-th prime for `n >= 6`.
+        fn first_primes(n: usize) -> Vec<u64> {
+            if n == 0 {
+                return Vec::new();
+            }
+            let limit = if n < 6 {
+                13
+            } else {
+                let n = n as f64;
+                (n * (n.ln() + n.ln().ln())).ceil() as usize
+            };
+
+            let mut composite = vec![false; limit + 1];
+            let mut primes = Vec::with_capacity(n);
+            for candidate in 2..=limit {
+                if composite[candidate] {
+                    continue;
+                }
+                primes.push(candidate as u64);
+                if primes.len() == n {
+                    break;
+                }
+                // A square past `usize::MAX` is past `limit` too.
+                if let Some(start) = candidate.checked_mul(candidate) {
+                    for multiple in (start..=limit).step_by(candidate) {
+                        composite[multiple] = true;
+                    }
+                }
+            }
+            primes
+        }
+
+        /// Deterministic Miller-Rabin; these bases suffice for every `u64`.
+        fn is_prime(n: u64) -> bool {
+            const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+            if n < 2 {
+                return false;
+            }
+            if let Some(&p) = BASES.iter().find(|&&p| n.is_multiple_of(p)) {
+                return n == p;
+            }
+            let mul_mod = |a: u64, b: u64| (a as u128 * b as u128 % n as u128) as u64;
+            let pow_mod = |mut base: u64, mut exp: u64| {
+                let mut result = 1;
+                while exp > 0 {
+                    if exp & 1 == 1 {
+                        result = mul_mod(result, base);
+                    }
+                    base = mul_mod(base, base);
+                    exp >>= 1;
+                }
+                result
+            };
+            let (d, s) = ((n - 1) >> (n - 1).trailing_zeros(), (n - 1).trailing_zeros());
+            BASES.iter().all(|&a| {
+                let mut x = pow_mod(a, d);
+                if x == 1 || x == n - 1 {
+                    return true;
+                }
+                (1..s).any(|_| {
+                    x = mul_mod(x, x);
+                    x == n - 1
+                })
+            })
+        }
+
+        /// A cortex that keeps at most `capacity` slots filled, evicting the
+        /// least-recently-accessed slot when a new one is filled past that.
+        ///
+        /// Reads through [`LruCortex::get`] and writes through
+        /// [`LruCortex::set_data`] both count as accesses. Evicted slots keep
+        /// their tag but lose their data.
+        #[derive(Debug)]
+        pub struct LruCortex {
+            cortex: MemoryCortex,
+            capacity: usize,
+            /// Filled slot indices, least recently used first.
+            recency: VecDeque<usize>,
+        }
+
+        impl LruCortex {
+            /// Wrap `cortex`, whose slots must all be empty, holding at most
+            /// `capacity` of them filled at once.
+            pub fn new(cortex: MemoryCortex, capacity: usize) -> UorResult<Self> {
+                if capacity == 0 {
+                    return Err(UorError::General("LruCortex capacity must be at least 1".into()));
+                }
+                if cortex.filled_count() > 0 {
+                    return Err(UorError::General(
+                        "LruCortex must start from an empty cortex".into(),
+                    ));
+                }
+                Ok(Self {
+                    cortex,
+                    capacity,
+                    recency: VecDeque::new(),
+                })
+            }
+
+            pub fn capacity(&self) -> usize {
+                self.capacity
+            }
+
+            /// The wrapped cortex, for read-only queries such as `sum`.
+            pub fn cortex(&self) -> &MemoryCortex {
+                &self.cortex
+            }
+
+            /// Read the data in slot `index`, marking it most recently used
+            /// if it is filled.
+            pub fn get(&mut self, index: usize) -> UorResult<Option<f64>> {
+                let data = self.cortex.slot_mut(index)?.data;
+                if data.is_some() {
+                    self.touch(index);
+                }
+                Ok(data)
+            }
+
+            /// Store `value` in slot `index`, marking it most recently used.
+            ///
+            /// Returns the index of the slot evicted to make room, if any.
+            pub fn set_data(&mut self, index: usize, value: f64) -> UorResult<Option<usize>> {
+                let slot = self.cortex.slot_mut(index)?;
+                let was_filled = slot.data.is_some();
+                slot.data = Some(value);
+                if was_filled {
+                    self.touch(index);
+                    return Ok(None);
+                }
+
+                self.recency.push_back(index);
+                if self.recency.len() <= self.capacity {
+                    return Ok(None);
+                }
+                let evicted = self.recency.pop_front();
+                if let Some(evicted) = evicted {
+                    self.cortex.references[evicted].data = None;
+                }
+                Ok(evicted)
+            }
+
+            fn touch(&mut self, index: usize) {
+                if let Some(position) = self.recency.iter().position(|&i| i == index) {
+                    self.recency.remove(position);
+                }
+                self.recency.push_back(index);
+            }
+        }
+    }
+
+    // 2.5. embedding
+    //      Defines how each manifold is embedded into higher-dimensional
+    //      quaternion (or Clifford) space, focusing on in-phase expansions.
+
+    /// The embedding module handles specialized expansions or embeddings
+    /// (e.g. quaternions, spinors, etc.).
+    ///
+    /// Each manifold can be embedded in up to 144 dimensions, referencing
+    /// the prime-based memory in `cortex`.
+    pub mod embedding {
+        use super::manifold::Manifold;
+        use super::cortex::MemoryCortex;
+        use super::{UorResult, UorError};
+        use std::collections::HashMap;
+        use std::ops::{Add, Mul};
+
+        /// A basic quaternion representation.
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub struct Quaternion {
+            pub w: f64,
+            pub x: f64,
+            pub y: f64,
+            pub z: f64,
+        }
+
+        impl Quaternion {
+            /// The identity rotation `1 + 0i + 0j + 0k`.
+            pub fn identity() -> Self {
+                Self { w: 1.0, x: 0.0, y: 0.0, z: 0.0 }
+            }
+
+            /// The conjugate `w - xi - yj - zk`; the inverse of a unit quaternion.
+            pub fn conjugate(&self) -> Self {
+                Self { w: self.w, x: -self.x, y: -self.y, z: -self.z }
+            }
+
+            /// The rotation taking `other` to `self`, i.e. `other⁻¹ * self`,
+            /// so that `other * self.relative_to(other)` is `self`.
+            ///
+            /// Both inputs are normalized first; a zero quaternion is treated
+            /// as the identity.
+            pub fn relative_to(&self, other: &Quaternion) -> Quaternion {
+                other.unit_or_identity().conjugate() * self.unit_or_identity()
+            }
+
+            /// `self` scaled to unit norm. Errors on the zero quaternion,
+            /// which has no direction.
+            pub fn normalize(&self) -> UorResult<Quaternion> {
+                let norm = self.norm();
+                if norm == 0.0 {
+                    return Err(UorError::General("Cannot normalize a zero quaternion".into()));
+                }
+                Ok(Quaternion { w: self.w / norm, x: self.x / norm, y: self.y / norm, z: self.z / norm })
+            }
+
+            /// `self` scaled to unit norm, or the identity if the norm is zero.
+            fn unit_or_identity(&self) -> Quaternion {
+                self.normalize().unwrap_or_else(|_| Quaternion::identity())
+            }
+
+            /// Four-dimensional dot product.
+            pub fn dot(&self, other: &Quaternion) -> f64 {
+                self.w * other.w + self.x * other.x + self.y * other.y + self.z * other.z
+            }
+
+            /// Euclidean norm.
+            pub fn norm(&self) -> f64 {
+                self.dot(self).sqrt()
+            }
+
+            /// `steps` unit quaternions spherically interpolated from `self`
+            /// to `other` at evenly spaced `t`, along the shorter arc.
+            ///
+            /// The path starts at `self` normalized and ends at `other`
+            /// normalized, or at `-other` (the same rotation) when that is
+            /// where the shorter arc leads, so no step jumps across the
+            /// sphere. Fewer than two steps can't include both endpoints: one
+            /// step gives just the start, zero gives nothing.
+            pub fn slerp_path(&self, other: &Quaternion, steps: usize) -> Vec<Quaternion> {
+                let (start, end) = self.arc_endpoints(other);
+                match steps {
+                    0 => Vec::new(),
+                    1 => vec![start],
+                    _ => {
+                        let mut path = vec![start];
+                        let last = (steps - 1) as f64;
+                        path.extend((1..steps - 1).map(|i| Self::slerp_unit(&start, &end, i as f64 / last)));
+                        path.push(end);
+                        path
+                    }
+                }
+            }
+
+            /// Spherical linear interpolation: the unit quaternion a fraction
+            /// `t` (clamped to `[0, 1]`) of the way from `self` to `other`,
+            /// at constant angular speed along the shorter arc.
+            ///
+            /// Both inputs are normalized first (a zero quaternion counts as
+            /// the identity). When the shorter arc leads to `-other`, samples
+            /// approach `-other` (the same rotation), but `t = 1` still gives
+            /// `other` itself. Nearly parallel inputs are blended linearly
+            /// instead, which avoids dividing by a vanishing sine.
+            pub fn slerp(&self, other: &Quaternion, t: f64) -> Quaternion {
+                let t = t.clamp(0.0, 1.0);
+                if t == 1.0 {
+                    return other.unit_or_identity();
+                }
+                let (a, b) = self.arc_endpoints(other);
+                Self::slerp_unit(&a, &b, t)
+            }
+
+            /// `self` and `other` normalized, with `other` negated when that
+            /// puts it on the shorter arc from `self`.
+            fn arc_endpoints(&self, other: &Quaternion) -> (Quaternion, Quaternion) {
+                let a = self.unit_or_identity();
+                let b = other.unit_or_identity();
+                if a.dot(&b) < 0.0 {
+                    (a, Quaternion { w: -b.w, x: -b.x, y: -b.y, z: -b.z })
+                } else {
+                    (a, b)
+                }
+            }
+
+            /// Slerp between unit quaternions from [`Quaternion::arc_endpoints`].
+            fn slerp_unit(a: &Quaternion, b: &Quaternion, t: f64) -> Quaternion {
+                let cos = a.dot(b);
+                let (wa, wb) = if cos > 0.9995 {
+                    // Nearly parallel: sin(theta) is too small to divide by.
+                    (1.0 - t, t)
+                } else {
+                    let theta = cos.acos();
+                    let sin = theta.sin();
+                    (((1.0 - t) * theta).sin() / sin, (t * theta).sin() / sin)
+                };
+                let blended = Quaternion {
+                    w: wa * a.w + wb * b.w,
+                    x: wa * a.x + wb * b.x,
+                    y: wa * a.y + wb * b.y,
+                    z: wa * a.z + wb * b.z,
+                };
+                blended.unit_or_identity()
+            }
+
+            /// `true` when every component is within `epsilon` of `other`'s.
+            pub fn approx_eq(&self, other: &Quaternion, epsilon: f64) -> bool {
+                (self.w - other.w).abs() <= epsilon
+                    && (self.x - other.x).abs() <= epsilon
+                    && (self.y - other.y).abs() <= epsilon
+                    && (self.z - other.z).abs() <= epsilon
+            }
+
+            /// Like [`Quaternion::approx_eq`], but also accepts `-other`, since
+            /// `q` and `-q` represent the same rotation.
+            pub fn approx_eq_rotation(&self, other: &Quaternion, epsilon: f64) -> bool {
+                let negated = Quaternion { w: -other.w, x: -other.x, y: -other.y, z: -other.z };
+                self.approx_eq(other, epsilon) || self.approx_eq(&negated, epsilon)
+            }
+        }
+
+        /// The Hamilton product, which composes rotations (`a * b` applies
+        /// `b` first). It is not commutative: `i * j = k` but `j * i = -k`.
+        impl Mul for Quaternion {
+            type Output = Quaternion;
+
+            fn mul(self, rhs: Quaternion) -> Quaternion {
+                Quaternion {
+                    w: self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z,
+                    x: self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+                    y: self.w * rhs.y - self.x * rhs.z + self.y * rhs.w + self.z * rhs.x,
+                    z: self.w * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.w,
+                }
+            }
+        }
+
+        /// Component-wise sum.
+        impl Add for Quaternion {
+            type Output = Quaternion;
+
+            fn add(self, rhs: Quaternion) -> Quaternion {
+                Quaternion {
+                    w: self.w + rhs.w,
+                    x: self.x + rhs.x,
+                    y: self.y + rhs.y,
+                    z: self.z + rhs.z,
+                }
+            }
+        }
+
+        /// Mean angular distance, in radians, between paired quaternions.
+        ///
+        /// Each pair is treated as two rotations, so `q` and `-q` are at
+        /// distance zero and the result lies in `[0, π]`. Errors if the slices
+        /// differ in length or a quaternion has zero norm; two empty slices
+        /// are at distance zero.
+        pub fn embedding_distance(a: &[Quaternion], b: &[Quaternion]) -> UorResult<f64> {
+            if a.len() != b.len() {
+                return Err(UorError::General(format!(
+                    "Cannot compare embeddings of different lengths ({} vs {})",
+                    a.len(),
+                    b.len()
+                )));
+            }
+            if a.is_empty() {
+                return Ok(0.0);
+            }
+
+            let mut total = 0.0;
+            for (p, q) in a.iter().zip(b) {
+                let norms = p.norm() * q.norm();
+                if norms == 0.0 {
+                    return Err(UorError::General(
+                        "Cannot measure the angle of a zero quaternion".into(),
+                    ));
+                }
+                let cos_half = (p.dot(q).abs() / norms).min(1.0);
+                total += 2.0 * cos_half.acos();
+            }
+            Ok(total / a.len() as f64)
+        }
+
+        /// Interface for embedding a manifold into a set of quaternions.
+        pub trait QuaternionEmbedding {
+            /// Perform an embedding of the given manifold using the
+            /// memory context, writing the quaternions into `out`.
+            ///
+            /// `out` is cleared first, so a caller can reuse the same
+            /// buffer across many embeddings without reallocating.
+            fn embed_into(
+                &self,
+                manifold: &Manifold,
+                cortex: &mut MemoryCortex,
+                out: &mut Vec<Quaternion>
+            ) -> UorResult<()>;
+
+            /// Perform an embedding of the given manifold using the
+            /// memory context, returning a set of quaternions.
+            fn embed_manifold(
+                &self,
+                manifold: &Manifold,
+                cortex: &mut MemoryCortex
+            ) -> UorResult<Vec<Quaternion>> {
+                let mut out = Vec::new();
+                self.embed_into(manifold, cortex, &mut out)?;
+                Ok(out)
+            }
+
+            /// Name of this embedding used when describing a stack.
+            fn kind(&self) -> &'static str {
+                std::any::type_name::<Self>()
+            }
+
+            /// A boxed copy of this embedding, or `None` (the default) if it
+            /// can't be copied.
+            fn clone_box(&self) -> Option<Box<dyn QuaternionEmbedding>> {
+                None
+            }
+
+            /// Reseed any randomness the embedding uses. The default does
+            /// nothing.
+            fn set_seed(&mut self, _seed: u64) {}
+
+            /// Do any expensive one-off setup ahead of the first embedding.
+            /// The default does nothing.
+            fn warmup(&mut self) -> UorResult<()> {
+                Ok(())
+            }
+        }
+
+        /// Embeds each node as the sum of a base quaternion and its
+        /// predecessors' quaternions, each scaled by the weight of the edge
+        /// it arrives along (1.0 unless set with
+        /// [`Manifold::set_edge_weight`]).
+        ///
+        /// A node whose data parses as a number `v` has base `(v, 0, 0, 0)`;
+        /// any other node has the identity. The output holds one quaternion
+        /// per node in topological order (ties broken by ID). Errors if the
+        /// manifold has a cycle.
+        #[derive(Clone, Default)]
+        pub struct WeightedTopologicalEmbedding {
+            /// Scale each output quaternion to unit norm. A zero quaternion
+            /// has no direction and becomes the identity.
+            pub normalize: bool,
+            /// Reject, with `UorError::EmbeddingError`, any node whose
+            /// quaternion has a norm below this, rather than emitting (or
+            /// normalizing) a near-zero result. The default of 0.0 accepts
+            /// everything.
+            pub min_norm: f64,
+        }
+
+        impl QuaternionEmbedding for WeightedTopologicalEmbedding {
+            fn embed_into(
+                &self,
+                manifold: &Manifold,
+                _cortex: &mut MemoryCortex,
+                out: &mut Vec<Quaternion>
+            ) -> UorResult<()> {
+                out.clear();
+                // Weighted sum of the quaternions arriving at each node so far.
+                let mut incoming: HashMap<&str, Quaternion> = HashMap::new();
+                for id in manifold.topological_order()? {
+                    let mut q = match manifold.nodes[id].value() {
+                        Some(v) => Quaternion { w: v, x: 0.0, y: 0.0, z: 0.0 },
+                        None => Quaternion::identity(),
+                    };
+                    if let Some(sum) = incoming.remove(id) {
+                        q = q + sum;
+                    }
+                    if q.norm() < self.min_norm {
+                        return Err(UorError::EmbeddingError(format!(
+                            "Node {} embeds with norm {} below the minimum {}",
+                            id,
+                            q.norm(),
+                            self.min_norm
+                        )));
+                    }
+                    out.push(if self.normalize { q.unit_or_identity() } else { q });
+
+                    for (to, weight) in manifold.weighted_targets(id) {
+                        let sum = incoming.entry(to).or_insert(Quaternion { w: 0.0, x: 0.0, y: 0.0, z: 0.0 });
+                        sum.w += weight * q.w;
+                        sum.x += weight * q.x;
+                        sum.y += weight * q.y;
+                        sum.z += weight * q.z;
+                    }
+                }
+                Ok(())
+            }
+
+            fn clone_box(&self) -> Option<Box<dyn QuaternionEmbedding>> {
+                Some(Box::new(self.clone()))
+            }
+        }
+
+        /// Example struct that implements the QuaternionEmbedding trait.
+        #[derive(Clone, Default)]
+        pub struct DefaultQuaternionEmbedding;
+
+        impl QuaternionEmbedding for DefaultQuaternionEmbedding {
+            fn embed_into(
+                &self,
+                _manifold: &Manifold,
+                _cortex: &mut MemoryCortex,
+                out: &mut Vec<Quaternion>
+            ) -> UorResult<()> {
+                // Real embedding logic would interpret manifold data
+                // and produce quaternions.
+                // Stub implementation:
+                out.clear();
+                out.push(Quaternion::identity());
+                Ok(())
+            }
+
+            fn clone_box(&self) -> Option<Box<dyn QuaternionEmbedding>> {
+                Some(Box::new(self.clone()))
+            }
+        }
+
+        /// An element of the Clifford algebra Cl(3,0): a scalar, a vector
+        /// (`e1`, `e2`, `e3`), a bivector (`e12`, `e13`, `e23`) and a
+        /// trivector (`e123`) part. The basis vectors square to +1.
+        #[derive(Debug, Clone, Copy, Default, PartialEq)]
+        pub struct Multivector {
+            pub scalar: f64,
+            pub e1: f64,
+            pub e2: f64,
+            pub e3: f64,
+            pub e12: f64,
+            pub e13: f64,
+            pub e23: f64,
+            pub e123: f64,
+        }
+
+        impl Multivector {
+            /// The scalar multivector `value`.
+            pub fn scalar(value: f64) -> Self {
+                Self { scalar: value, ..Self::default() }
+            }
+
+            /// The vector `x e1 + y e2 + z e3`.
+            pub fn vector(x: f64, y: f64, z: f64) -> Self {
+                Self { e1: x, e2: y, e3: z, ..Self::default() }
+            }
+
+            /// The geometric product `self * other`.
+            pub fn geometric_product(&self, other: &Multivector) -> Multivector {
+                let (a, b) = (self.blades(), other.blades());
+                let mut product = [0.0; 8];
+                for (i, &x) in a.iter().enumerate() {
+                    for (j, &y) in b.iter().enumerate() {
+                        product[i ^ j] += Self::reorder_sign(i, j) * x * y;
+                    }
+                }
+                Self::from_blades(product)
+            }
+
+            /// Components indexed by blade bitmask: bit 0 is `e1`, bit 1 `e2`
+            /// and bit 2 `e3`, so index 3 is `e12` and 7 is `e123`.
+            fn blades(&self) -> [f64; 8] {
+                [self.scalar, self.e1, self.e2, self.e12, self.e3, self.e13, self.e23, self.e123]
+            }
+
+            fn from_blades(b: [f64; 8]) -> Self {
+                Self { scalar: b[0], e1: b[1], e2: b[2], e12: b[3], e3: b[4], e13: b[5], e23: b[6], e123: b[7] }
+            }
+
+            /// The sign picked up by sorting the basis vectors of blade `a`
+            /// followed by blade `b` into canonical order.
+            fn reorder_sign(a: usize, b: usize) -> f64 {
+                let swaps: u32 = (1..3).map(|shift| ((a >> shift) & b).count_ones()).sum();
+                if swaps.is_multiple_of(2) { 1.0 } else { -1.0 }
+            }
+        }
+
+        /// The geometric product, see [`Multivector::geometric_product`].
+        impl Mul for Multivector {
+            type Output = Multivector;
+
+            fn mul(self, rhs: Multivector) -> Multivector {
+                self.geometric_product(&rhs)
+            }
+        }
+
+        /// Component-wise sum.
+        impl Add for Multivector {
+            type Output = Multivector;
+
+            fn add(self, rhs: Multivector) -> Multivector {
+                let (a, b) = (self.blades(), rhs.blades());
+                Self::from_blades(std::array::from_fn(|i| a[i] + b[i]))
+            }
+        }
+
+        /// Interface for embedding a manifold into a set of Cl(3,0)
+        /// multivectors, the counterpart of [`QuaternionEmbedding`].
+        pub trait CliffordEmbedding {
+            /// Perform an embedding of the given manifold using the
+            /// memory context, writing the multivectors into `out`.
+            ///
+            /// `out` is cleared first, so a caller can reuse the same
+            /// buffer across many embeddings without reallocating.
+            fn embed_into(
+                &self,
+                manifold: &Manifold,
+                cortex: &mut MemoryCortex,
+                out: &mut Vec<Multivector>
+            ) -> UorResult<()>;
+
+            /// Perform an embedding of the given manifold using the
+            /// memory context, returning a set of multivectors.
+            fn embed_manifold(
+                &self,
+                manifold: &Manifold,
+                cortex: &mut MemoryCortex
+            ) -> UorResult<Vec<Multivector>> {
+                let mut out = Vec::new();
+                self.embed_into(manifold, cortex, &mut out)?;
+                Ok(out)
+            }
+
+            /// Name of this embedding used when describing it.
+            fn kind(&self) -> &'static str {
+                std::any::type_name::<Self>()
+            }
+
+            /// A boxed copy of this embedding, or `None` (the default) if it
+            /// can't be copied.
+            fn clone_box(&self) -> Option<Box<dyn CliffordEmbedding>> {
+                None
+            }
+
+            /// Reseed any randomness the embedding uses. The default does
+            /// nothing.
+            fn set_seed(&mut self, _seed: u64) {}
+
+            /// Do any expensive one-off setup ahead of the first embedding.
+            /// The default does nothing.
+            fn warmup(&mut self) -> UorResult<()> {
+                Ok(())
+            }
+        }
+
+        /// Example struct that implements the CliffordEmbedding trait.
+        #[derive(Clone, Default)]
+        pub struct DefaultCliffordEmbedding;
+
+        impl CliffordEmbedding for DefaultCliffordEmbedding {
+            fn embed_into(
+                &self,
+                _manifold: &Manifold,
+                _cortex: &mut MemoryCortex,
+                out: &mut Vec<Multivector>
+            ) -> UorResult<()> {
+                // Stub implementation, like DefaultQuaternionEmbedding:
+                out.clear();
+                out.push(Multivector::scalar(1.0));
+                Ok(())
+            }
+
+            fn clone_box(&self) -> Option<Box<dyn CliffordEmbedding>> {
+                Some(Box::new(self.clone()))
+            }
+        }
+    }
+
+    // 2.6. operators
+    //      Provides HPC-oriented transformations, like partial sums, spinor
+    //      operators, concurrency distribution, or Lie group exponentials.
+
+    /// The operators module encapsulates advanced HPC or mathematical
+    /// transformations that can be applied to embedded manifolds.
+    pub mod operators {
+        use super::chart::Chart;
+        use super::manifold::{Edge, Manifold};
+        use super::{rng, UorResult, UorError};
+        use std::collections::HashMap;
+
+        /// A trait for HPC operators or transformations on Manifolds.
+        pub trait HpcOperator {
+            fn apply(&self, manifold: &Manifold) -> UorResult<Manifold>;
+
+            /// Name of this operator used when describing a stack.
+            fn kind(&self) -> &'static str {
+                std::any::type_name::<Self>()
+            }
+
+            /// A boxed copy of this operator, or `None` (the default) if it
+            /// can't be copied.
+            fn clone_box(&self) -> Option<Box<dyn HpcOperator>> {
+                None
+            }
+
+            /// Reseed any randomness the operator uses. The default does
+            /// nothing.
+            fn set_seed(&mut self, _seed: u64) {}
+
+            /// Do any expensive one-off setup ahead of the first `apply`.
+            /// The default does nothing.
+            fn warmup(&mut self) -> UorResult<()> {
+                Ok(())
+            }
+        }
+
+        /// Example operator for demonstration.
+        #[derive(Clone, Default)]
+        pub struct ExampleOperator;
+
+        impl HpcOperator for ExampleOperator {
+            fn apply(&self, manifold: &Manifold) -> UorResult<Manifold> {
+                // HPC logic or concurrency logic to transform manifold
+                Ok(manifold.clone())
+            }
+
+            fn clone_box(&self) -> Option<Box<dyn HpcOperator>> {
+                Some(Box::new(self.clone()))
+            }
+        }
+
+        /// Clusters numeric nodes with one-dimensional k-means.
+        ///
+        /// Each node whose data parses as a number is treated as a point; the
+        /// assigned cluster index is written to its `"cluster"` metadata.
+        /// Clusters are numbered by ascending centroid, and initialization is
+        /// driven by `seed`, so results are deterministic. `k` is capped at
+        /// the number of distinct values; non-numeric nodes are left alone.
+        #[derive(Clone)]
+        pub struct KMeansOperator {
+            pub k: usize,
+            pub seed: u64,
+            pub max_iterations: usize,
+        }
+
+        impl KMeansOperator {
+            pub fn new(k: usize, seed: u64) -> Self {
+                Self { k, seed, max_iterations: 100 }
+            }
+        }
+
+        impl HpcOperator for KMeansOperator {
+            fn apply(&self, manifold: &Manifold) -> UorResult<Manifold> {
+                if self.k == 0 {
+                    return Err(UorError::General("KMeansOperator needs k >= 1".into()));
+                }
+
+                let mut points: Vec<(String, f64)> = manifold
+                    .nodes
+                    .values()
+                    .filter_map(|node| node.value().map(|v| (node.id.clone(), v)))
+                    .collect();
+                points.sort_by(|a, b| a.0.cmp(&b.0));
+
+                let mut distinct: Vec<f64> = points.iter().map(|(_, v)| *v).collect();
+                distinct.sort_by(f64::total_cmp);
+                distinct.dedup();
+                let k = self.k.min(distinct.len());
+                let mut output = manifold.clone();
+                if k == 0 {
+                    return Ok(output);
+                }
+
+                // Seeded Fisher-Yates shuffle, then take the first k values.
+                let mut state = self.seed;
+                for i in (1..distinct.len()).rev() {
+                    let j = (rng::splitmix64(&mut state) % (i as u64 + 1)) as usize;
+                    distinct.swap(i, j);
+                }
+                let mut centroids: Vec<f64> = distinct[..k].to_vec();
+
+                let nearest = |centroids: &[f64], value: f64| -> usize {
+                    let mut best = 0;
+                    for (c, centroid) in centroids.iter().enumerate() {
+                        if (value - centroid).abs() < (value - centroids[best]).abs() {
+                            best = c;
+                        }
+                    }
+                    best
+                };
+
+                let mut assignment = vec![0; points.len()];
+                for _ in 0..self.max_iterations {
+                    for (slot, (_, value)) in assignment.iter_mut().zip(&points) {
+                        *slot = nearest(&centroids, *value);
+                    }
+                    let mut next = centroids.clone();
+                    for (c, centroid) in next.iter_mut().enumerate() {
+                        let members: Vec<f64> = points
+                            .iter()
+                            .zip(&assignment)
+                            .filter(|(_, &a)| a == c)
+                            .map(|((_, v), _)| *v)
+                            .collect();
+                        if !members.is_empty() {
+                            *centroid = members.iter().sum::<f64>() / members.len() as f64;
+                        }
+                    }
+                    if next == centroids {
+                        break;
+                    }
+                    centroids = next;
+                }
+
+                // Renumber clusters by ascending centroid.
+                let mut by_position: Vec<usize> = (0..k).collect();
+                by_position.sort_by(|&a, &b| centroids[a].total_cmp(&centroids[b]));
+                let mut rank = vec![0; k];
+                for (position, &c) in by_position.iter().enumerate() {
+                    rank[c] = position;
+                }
+
+                for ((id, _), &c) in points.iter().zip(&assignment) {
+                    if let Some(node) = output.nodes.get_mut(id) {
+                        node.metadata.insert("cluster".into(), rank[c].to_string());
+                    }
+                }
+                Ok(output)
+            }
+
+            fn clone_box(&self) -> Option<Box<dyn HpcOperator>> {
+                Some(Box::new(self.clone()))
+            }
+
+            fn set_seed(&mut self, seed: u64) {
+                self.seed = seed;
+            }
+        }
+
+        /// Makes a graph acyclic by repeatedly finding a cycle (via
+        /// [`Manifold::find_cycle`]) and removing the edge that closes it.
+        ///
+        /// The removed edges are recorded, in removal order, in the result's
+        /// `"removed_edges"` metadata as `from->to` entries joined by `, `.
+        /// An acyclic input is returned unchanged.
+        #[derive(Clone, Default)]
+        pub struct CycleBreakerOperator;
+
+        impl HpcOperator for CycleBreakerOperator {
+            fn apply(&self, manifold: &Manifold) -> UorResult<Manifold> {
+                let mut output = manifold.clone();
+                let mut removed = Vec::new();
+                while let Some(cycle) = output.find_cycle() {
+                    let from = cycle.last().expect("cycles are non-empty").clone();
+                    let to = cycle[0].clone();
+                    output.remove_edge(&from, &to)?;
+                    removed.push(format!("{}->{}", from, to));
+                }
+                if !removed.is_empty() {
+                    output.metadata.insert("removed_edges".into(), removed.join(", "));
+                }
+                Ok(output)
+            }
+
+            fn clone_box(&self) -> Option<Box<dyn HpcOperator>> {
+                Some(Box::new(self.clone()))
+            }
+        }
+
+        /// Labels each node with the index of its weakly-connected component
+        /// (see [`Manifold::weakly_connected_components`]) in its
+        /// `"component"` metadata.
+        #[derive(Clone, Default)]
+        pub struct ComponentLabelOperator;
+
+        impl HpcOperator for ComponentLabelOperator {
+            fn apply(&self, manifold: &Manifold) -> UorResult<Manifold> {
+                let mut output = manifold.clone();
+                for (index, component) in manifold.weakly_connected_components().iter().enumerate() {
+                    for id in component {
+                        output.get_node_mut(id)?.metadata.insert("component".into(), index.to_string());
+                    }
+                }
+                Ok(output)
+            }
+
+            fn clone_box(&self) -> Option<Box<dyn HpcOperator>> {
+                Some(Box::new(self.clone()))
+            }
+        }
+
+        /// Writes each node's [`Manifold::layer_assignment`] depth into its
+        /// `"depth"` metadata. Errors on cyclic input.
+        #[derive(Clone, Default)]
+        pub struct DepthOperator;
+
+        impl HpcOperator for DepthOperator {
+            fn apply(&self, manifold: &Manifold) -> UorResult<Manifold> {
+                let mut output = manifold.clone();
+                for (id, depth) in manifold.layer_assignment()? {
+                    output.get_node_mut(&id)?.metadata.insert("depth".into(), depth.to_string());
+                }
+                Ok(output)
+            }
+
+            fn clone_box(&self) -> Option<Box<dyn HpcOperator>> {
+                Some(Box::new(self.clone()))
+            }
+        }
+
+        /// Renames the nodes `"0"`, `"1"`, ... in [`Manifold::topological_sort`]
+        /// order, so sources get the lowest IDs, using [`RelabelOperator`].
+        ///
+        /// The renaming is recorded, in the new ID order, in the result's
+        /// `"relabeled"` metadata as `old->new` entries joined by `, `.
+        /// Errors on cyclic input.
+        #[derive(Clone, Default)]
+        pub struct NumericRelabelOperator;
+
+        impl HpcOperator for NumericRelabelOperator {
+            fn apply(&self, manifold: &Manifold) -> UorResult<Manifold> {
+                let order = manifold.topological_order()?;
+                let mapping: HashMap<String, String> =
+                    order.iter().enumerate().map(|(index, &id)| (id.to_string(), index.to_string())).collect();
+                let renamed: Vec<String> =
+                    order.iter().map(|&id| format!("{}->{}", id, mapping[id])).collect();
+                let mut output = RelabelOperator::new(mapping).apply(manifold)?;
+                output.metadata.insert("relabeled".into(), renamed.join(", "));
+                Ok(output)
+            }
+
+            fn clone_box(&self) -> Option<Box<dyn HpcOperator>> {
+                Some(Box::new(self.clone()))
+            }
+        }
+
+        /// Writes each node's degree into its `"in_degree"` and
+        /// `"out_degree"` metadata, counting parallel edges separately.
+        ///
+        /// Edges to missing nodes are not counted.
+        #[derive(Clone, Default)]
+        pub struct DegreeFeatureOperator;
+
+        impl HpcOperator for DegreeFeatureOperator {
+            fn apply(&self, manifold: &Manifold) -> UorResult<Manifold> {
+                let mut degrees: HashMap<&str, (usize, usize)> =
+                    manifold.nodes.keys().map(|id| (id.as_str(), (0, 0))).collect();
+                for from in manifold.nodes.keys() {
+                    for to in manifold.targets(from) {
+                        degrees.get_mut(from.as_str()).expect("source exists").1 += 1;
+                        degrees.get_mut(to).expect("targets resolve").0 += 1;
+                    }
+                }
+
+                let mut output = manifold.clone();
+                for (id, (in_degree, out_degree)) in degrees {
+                    let node = output.get_node_mut(id)?;
+                    node.metadata.insert("in_degree".into(), in_degree.to_string());
+                    node.metadata.insert("out_degree".into(), out_degree.to_string());
+                }
+                Ok(output)
+            }
+
+            fn clone_box(&self) -> Option<Box<dyn HpcOperator>> {
+                Some(Box::new(self.clone()))
+            }
+        }
+
+        /// Writes each node's PageRank into its `"pagerank"` metadata. Ranks
+        /// sum to 1.
+        ///
+        /// Each node passes `damping` of its rank along its out-edges, split
+        /// evenly per edge copy or, with `weighted`, in proportion to each
+        /// copy's [`Edge::weight`]. Nodes with no out-edges (or, when
+        /// weighted, a total out-weight of zero) spread their rank over every
+        /// node. Iterates until the total change drops below `tolerance` or
+        /// `max_iterations` is reached.
+        #[derive(Clone)]
+        pub struct PageRankOperator {
+            pub damping: f64,
+            pub max_iterations: usize,
+            pub tolerance: f64,
+            pub weighted: bool,
+        }
+
+        impl PageRankOperator {
+            pub fn new() -> Self {
+                Self {
+                    damping: 0.85,
+                    max_iterations: 100,
+                    tolerance: 1e-10,
+                    weighted: false,
+                }
+            }
+        }
+
+        impl Default for PageRankOperator {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl HpcOperator for PageRankOperator {
+            fn apply(&self, manifold: &Manifold) -> UorResult<Manifold> {
+                if !(0.0..=1.0).contains(&self.damping) {
+                    return Err(UorError::General(format!(
+                        "PageRank damping must be in [0, 1], got {}",
+                        self.damping
+                    )));
+                }
+                let ids = manifold.sorted_ids();
+                let n = ids.len();
+                let mut output = manifold.clone();
+                if n == 0 {
+                    return Ok(output);
+                }
+                let index: HashMap<&str, usize> = ids.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+
+                // Each node's out-edges as (target, share of its rank).
+                let mut shares: Vec<Vec<(usize, f64)>> = Vec::with_capacity(n);
+                for &from in &ids {
+                    let mut out: Vec<(usize, f64)> = Vec::new();
+                    for (to, weight) in manifold.weighted_targets(from) {
+                        let weight = if self.weighted { weight } else { 1.0 };
+                        if weight < 0.0 {
+                            return Err(UorError::General(format!(
+                                "PageRank needs non-negative weights, but {} -> {} has {}",
+                                from, to, weight
+                            )));
+                        }
+                        out.push((index[to], weight));
+                    }
+                    let total: f64 = out.iter().map(|(_, w)| w).sum();
+                    if total > 0.0 {
+                        out.iter_mut().for_each(|(_, w)| *w /= total);
+                    } else {
+                        out.clear();
+                    }
+                    shares.push(out);
+                }
+
+                let mut rank = vec![1.0 / n as f64; n];
+                for _ in 0..self.max_iterations {
+                    let dangling: f64 = (0..n).filter(|&i| shares[i].is_empty()).map(|i| rank[i]).sum();
+                    let base = (1.0 - self.damping + self.damping * dangling) / n as f64;
+                    let mut next = vec![base; n];
+                    for (from, out) in shares.iter().enumerate() {
+                        for &(to, share) in out {
+                            next[to] += self.damping * rank[from] * share;
+                        }
+                    }
+                    let change: f64 = next.iter().zip(&rank).map(|(a, b)| (a - b).abs()).sum();
+                    rank = next;
+                    if change < self.tolerance {
+                        break;
+                    }
+                }
+
+                for (&id, value) in ids.iter().zip(rank) {
+                    output.get_node_mut(id)?.metadata.insert("pagerank".into(), value.to_string());
+                }
+                Ok(output)
+            }
+
+            fn clone_box(&self) -> Option<Box<dyn HpcOperator>> {
+                Some(Box::new(self.clone()))
+            }
+        }
+
+        /// Merges parallel edges into one edge per node pair, weighted by the
+        /// sum of the originals' weights. The merged edge takes the place of
+        /// the pair's first copy; pairs with a single edge are untouched.
+        ///
+        /// The output has [`Manifold::simple`] set, so it stays collapsed.
+        #[derive(Clone, Default)]
+        pub struct CollapseParallelOperator;
+
+        impl HpcOperator for CollapseParallelOperator {
+            fn apply(&self, manifold: &Manifold) -> UorResult<Manifold> {
+                let mut output = manifold.clone();
+                for targets in output.edges.values_mut() {
+                    let mut position: HashMap<String, usize> = HashMap::new();
+                    let mut unique: Vec<Edge> = Vec::new();
+                    for edge in targets.drain(..) {
+                        match position.get(&edge.to) {
+                            Some(&index) => unique[index].weight += edge.weight,
+                            None => {
+                                position.insert(edge.to.clone(), unique.len());
+                                unique.push(edge);
+                            }
+                        }
+                    }
+                    *targets = unique;
+                }
+                output.simple = true;
+                Ok(output)
+            }
+
+            fn clone_box(&self) -> Option<Box<dyn HpcOperator>> {
+                Some(Box::new(self.clone()))
+            }
+        }
+
+        /// Sparsifies a graph by keeping at most `max_out_degree` outgoing
+        /// edges per node: the heaviest, ties going to the lower target ID.
+        /// Parallel edges count separately.
+        ///
+        /// Kept edges stay in their original order.
+        #[derive(Clone)]
+        pub struct MaxDegreeOperator {
+            pub max_out_degree: usize,
+        }
+
+        impl MaxDegreeOperator {
+            pub fn new(max_out_degree: usize) -> Self {
+                Self { max_out_degree }
+            }
+        }
+
+        impl HpcOperator for MaxDegreeOperator {
+            fn apply(&self, manifold: &Manifold) -> UorResult<Manifold> {
+                let mut output = manifold.clone();
+                for targets in output.edges.values_mut() {
+                    if targets.len() <= self.max_out_degree {
+                        continue;
+                    }
+                    let mut ranked: Vec<usize> = (0..targets.len()).collect();
+                    ranked.sort_by(|&a, &b| {
+                        let (a, b) = (&targets[a], &targets[b]);
+                        b.weight.total_cmp(&a.weight).then(a.to.cmp(&b.to))
+                    });
+                    let mut kept = vec![false; targets.len()];
+                    for &index in &ranked[..self.max_out_degree] {
+                        kept[index] = true;
+                    }
+                    let mut index = 0;
+                    targets.retain(|_| {
+                        index += 1;
+                        kept[index - 1]
+                    });
+                }
+                output.edges.retain(|_, targets| !targets.is_empty());
+                Ok(output)
+            }
+
+            fn clone_box(&self) -> Option<Box<dyn HpcOperator>> {
+                Some(Box::new(self.clone()))
+            }
+        }
+
+        /// Renames nodes through `mapping` and rewrites every edge to follow.
+        ///
+        /// Unmapped nodes keep their IDs. Errors if two distinct nodes would
+        /// end up with the same ID.
+        #[derive(Clone)]
+        pub struct RelabelOperator {
+            pub mapping: HashMap<String, String>,
+        }
+
+        impl RelabelOperator {
+            pub fn new(mapping: HashMap<String, String>) -> Self {
+                Self { mapping }
+            }
+
+            fn rename<'a>(&'a self, id: &'a str) -> &'a str {
+                self.mapping.get(id).map(String::as_str).unwrap_or(id)
+            }
+        }
+
+        impl HpcOperator for RelabelOperator {
+            fn apply(&self, manifold: &Manifold) -> UorResult<Manifold> {
+                let mut claimed: HashMap<&str, &str> = HashMap::new();
+                for id in manifold.nodes.keys() {
+                    let new_id = self.rename(id);
+                    if let Some(other) = claimed.insert(new_id, id) {
+                        let (a, b) = if other < id.as_str() { (other, id.as_str()) } else { (id.as_str(), other) };
+                        return Err(UorError::General(format!(
+                            "Relabeling maps both {} and {} to {}",
+                            a, b, new_id
+                        )));
+                    }
+                }
+
+                let mut output = manifold.clone();
+                output.nodes = manifold
+                    .nodes
+                    .values()
+                    .map(|node| {
+                        let mut node = node.clone();
+                        node.id = self.rename(&node.id).to_string();
+                        (node.id.clone(), node)
                     })
                     .collect();
-                Self { references: refs }
+                output.edges = manifold
+                    .edges
+                    .iter()
+                    .map(|(from, targets)| {
+                        let targets = targets
+                            .iter()
+                            .map(|edge| Edge::weighted(self.rename(&edge.to), edge.weight))
+                            .collect();
+                        (self.rename(from).to_string(), targets)
+                    })
+                    .collect();
+                Ok(output)
             }
 
-            /// Link the manifold’s data to the prime references in some way.
-            pub fn link_manifold(&mut self, _manifold: &Manifold) -> UorResult<()> {
-                // Implementation is domain-specific
-                Ok(())
+            fn clone_box(&self) -> Option<Box<dyn HpcOperator>> {
+                Some(Box::new(self.clone()))
             }
         }
-    }
 
-    // 2.5. embedding
-    //      Defines how each manifold is embedded into higher-dimensional
-    //      quaternion (or Clifford) space, focusing on in-phase expansions.
+        /// Round-trips the manifold through [`Manifold::to_json`] to reset
+        /// its internal state: empty adjacency lists are dropped, and each
+        /// adjacency list is sorted by target, then weight. Parallel edges
+        /// are kept, so the output is `==` to the input.
+        ///
+        /// Errors if an edge points at a missing node.
+        #[derive(Clone, Default)]
+        pub struct CanonicalizeOperator;
 
-    /// The embedding module handles specialized expansions or embeddings
-    /// (e.g. quaternions, spinors, etc.).
-    ///
-    /// Each manifold can be embedded in up to 144 dimensions, referencing
-    /// the prime-based memory in `cortex`.
-    pub mod embedding {
-        use super::manifold::Manifold;
-        use super::cortex::MemoryCortex;
-        use super::UorResult;
+        impl HpcOperator for CanonicalizeOperator {
+            fn apply(&self, manifold: &Manifold) -> UorResult<Manifold> {
+                let mut output = Manifold::from_json(&manifold.to_json()?)?;
+                for targets in output.edges.values_mut() {
+                    targets.sort_by(|a, b| a.to.cmp(&b.to).then(a.weight.total_cmp(&b.weight)));
+                }
+                output.strict_dag = manifold.strict_dag;
+                output.simple = manifold.simple;
+                Ok(output)
+            }
 
-        /// A basic quaternion representation.
-        #[derive(Debug, Clone)]
-        pub struct Quaternion {
-            pub w: f64,
-            pub x: f64,
-            pub y: f64,
-            pub z: f64,
+            fn clone_box(&self) -> Option<Box<dyn HpcOperator>> {
+                Some(Box::new(self.clone()))
+            }
         }
 
-        /// Interface for embedding a manifold into a set of quaternions.
-        pub trait QuaternionEmbedding {
-            /// Perform an embedding of the given manifold using the
-            /// memory context, returning a set of quaternions.
-            fn embed_manifold(
-                &self,
-                manifold: &Manifold,
-                cortex: &mut MemoryCortex
-            ) -> UorResult<Vec<Quaternion>>;
+        /// Checks that a manifold still has the structure a chart expects,
+        /// passing it through unchanged if so.
+        ///
+        /// Errors listing every violated constraint otherwise.
+        #[derive(Clone, Default)]
+        pub struct SchemaValidateOperator {
+            /// Node IDs that must be present.
+            pub required_nodes: Vec<String>,
+            /// Most nodes the manifold may have, if limited.
+            pub max_nodes: Option<usize>,
         }
 
-        /// Example struct that implements the QuaternionEmbedding trait.
+        impl SchemaValidateOperator {
+            /// Read the constraints from a chart's top-level
+            /// `required_nodes` (an array of IDs) and `max_nodes` fields,
+            /// either of which may be left out.
+            pub fn from_chart(chart: &Chart) -> UorResult<Self> {
+                let invalid = |what: &str| {
+                    UorError::ChartError(format!("Chart {} has an invalid `{}` constraint", chart.name, what))
+                };
+                let required_nodes = match chart.get_field("/required_nodes") {
+                    None => Vec::new(),
+                    Some(value) => value
+                        .as_array()
+                        .and_then(|ids| ids.iter().map(|id| id.as_str().map(String::from)).collect())
+                        .ok_or_else(|| invalid("required_nodes"))?,
+                };
+                let max_nodes = match chart.get_field("/max_nodes") {
+                    None => None,
+                    Some(value) => Some(value.as_u64().ok_or_else(|| invalid("max_nodes"))? as usize),
+                };
+                Ok(Self { required_nodes, max_nodes })
+            }
+        }
+
+        impl HpcOperator for SchemaValidateOperator {
+            fn apply(&self, manifold: &Manifold) -> UorResult<Manifold> {
+                let mut violations: Vec<String> = self
+                    .required_nodes
+                    .iter()
+                    .filter(|id| !manifold.nodes.contains_key(*id))
+                    .map(|id| format!("required node {} is missing", id))
+                    .collect();
+                if let Some(max) = self.max_nodes.filter(|&max| manifold.nodes.len() > max) {
+                    violations.push(format!(
+                        "{} nodes exceed the maximum of {}",
+                        manifold.nodes.len(),
+                        max
+                    ));
+                }
+                if !violations.is_empty() {
+                    return Err(UorError::General(format!(
+                        "Manifold does not match its schema: {}",
+                        violations.join("; ")
+                    )));
+                }
+                Ok(manifold.clone())
+            }
+
+            fn clone_box(&self) -> Option<Box<dyn HpcOperator>> {
+                Some(Box::new(self.clone()))
+            }
+        }
+
+        /// A transparent tap: applies the inner operator, shows the result to
+        /// `observer`, then returns it unchanged.
+        ///
+        /// Useful for watching one stage of a longer pipeline. The observer
+        /// is not called when the inner operator fails. Its `kind` names the
+        /// observer itself; ask `inner` for the wrapped operator's.
+        pub struct ObserverOperator {
+            pub inner: Box<dyn HpcOperator>,
+            pub observer: Box<dyn Fn(&Manifold)>,
+        }
+
+        impl ObserverOperator {
+            pub fn new(inner: Box<dyn HpcOperator>, observer: Box<dyn Fn(&Manifold)>) -> Self {
+                Self { inner, observer }
+            }
+        }
+
+        impl HpcOperator for ObserverOperator {
+            fn apply(&self, manifold: &Manifold) -> UorResult<Manifold> {
+                let output = self.inner.apply(manifold)?;
+                (self.observer)(&output);
+                Ok(output)
+            }
+
+            fn set_seed(&mut self, seed: u64) {
+                self.inner.set_seed(seed);
+            }
+
+            fn warmup(&mut self) -> UorResult<()> {
+                self.inner.warmup()
+            }
+        }
+
+        /// Applies a sequence of operators, each to the previous one's output.
+        ///
+        /// An error from a stage is returned as-is apart from a
+        /// `"Operator stage {index} failed"` prefix; later stages don't run.
         #[derive(Default)]
-        pub struct DefaultQuaternionEmbedding;
+        pub struct CompositeOperator {
+            pub stages: Vec<Box<dyn HpcOperator>>,
+            /// Return an empty manifold as-is without running any stage.
+            /// Off by default; only enable it when every stage leaves an
+            /// empty graph unchanged.
+            pub short_circuit_empty: bool,
+        }
 
-        impl QuaternionEmbedding for DefaultQuaternionEmbedding {
-            fn embed_manifold(
-                &self,
-                _manifold: &Manifold,
-                _cortex: &mut MemoryCortex
-            ) -> UorResult<Vec<Quaternion>> {
-                // Real embedding logic would interpret manifold data
-                // and produce quaternions.
-                // Stub implementation:
-                let q = Quaternion { w: 1.0, x: 0.0, y: 0.0, z: 0.0 };
-                Ok(vec![q])
+        impl CompositeOperator {
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            /// Append a stage to the end of the pipeline.
+            pub fn then(mut self, op: Box<dyn HpcOperator>) -> Self {
+                self.stages.push(op);
+                self
             }
         }
-    }
 
-    // 2.6. operators
-    //      Provides HPC-oriented transformations, like partial sums, spinor
-    //      operators, concurrency distribution, or Lie group exponentials.
+        impl HpcOperator for CompositeOperator {
+            fn apply(&self, manifold: &Manifold) -> UorResult<Manifold> {
+                let mut current = manifold.clone();
+                if self.short_circuit_empty && current.nodes.is_empty() {
+                    return Ok(current);
+                }
+                for (index, op) in self.stages.iter().enumerate() {
+                    current = op
+                        .apply(&current)
+                        .map_err(|e| e.with_context(&format!("Operator stage {} failed", index)))?;
+                }
+                Ok(current)
+            }
 
-    /// The operators module encapsulates advanced HPC or mathematical
-    /// transformations that can be applied to embedded manifolds.
-    pub mod operators {
-        use super::manifold::Manifold;
-        use super::UorResult;
+            fn clone_box(&self) -> Option<Box<dyn HpcOperator>> {
+                let stages = self.stages.iter().map(|op| op.clone_box()).collect::<Option<Vec<_>>>()?;
+                Some(Box::new(CompositeOperator {
+                    stages,
+                    short_circuit_empty: self.short_circuit_empty,
+                }))
+            }
 
-        /// A trait for HPC operators or transformations on Manifolds.
-        pub trait HpcOperator {
-            fn apply(&self, manifold: &Manifold) -> UorResult<Manifold>;
+            fn set_seed(&mut self, seed: u64) {
+                for op in &mut self.stages {
+                    op.set_seed(seed);
+                }
+            }
+
+            fn warmup(&mut self) -> UorResult<()> {
+                for op in &mut self.stages {
+                    op.warmup()?;
+                }
+                Ok(())
+            }
         }
 
-        /// Example operator for demonstration.
+        /// An operator pipeline whose stages carry names, so a prefix of the
+        /// pipeline can be run on its own (e.g. to bisect which stage
+        /// introduces a problem). A failing stage's error is prefixed with
+        /// its name.
         #[derive(Default)]
-        pub struct ExampleOperator;
+        pub struct NamedCompositeOperator {
+            pub stages: Vec<(String, Box<dyn HpcOperator>)>,
+            /// Return an empty manifold as-is without running any stage.
+            /// Off by default; only enable it when every stage leaves an
+            /// empty graph unchanged.
+            pub short_circuit_empty: bool,
+        }
+
+        impl NamedCompositeOperator {
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            /// Append a named stage to the end of the pipeline.
+            pub fn with_stage(mut self, name: &str, op: Box<dyn HpcOperator>) -> Self {
+                self.stages.push((name.into(), op));
+                self
+            }
+
+            /// Apply the stages in order, stopping after (and including) the
+            /// first stage called `stage_name`.
+            ///
+            /// Errors before running anything if no stage has that name.
+            pub fn run_until(&self, manifold: &Manifold, stage_name: &str) -> UorResult<Manifold> {
+                let last = self
+                    .stages
+                    .iter()
+                    .position(|(name, _)| name == stage_name)
+                    .ok_or_else(|| {
+                        UorError::General(format!("No operator stage named {}", stage_name))
+                    })?;
+
+                self.run(manifold, &self.stages[..=last])
+            }
+
+            fn run(&self, manifold: &Manifold, stages: &[(String, Box<dyn HpcOperator>)]) -> UorResult<Manifold> {
+                let mut current = manifold.clone();
+                if self.short_circuit_empty && current.nodes.is_empty() {
+                    return Ok(current);
+                }
+                for (name, op) in stages {
+                    current = op
+                        .apply(&current)
+                        .map_err(|e| e.with_context(&format!("Operator stage {} failed", name)))?;
+                }
+                Ok(current)
+            }
+        }
+
+        impl HpcOperator for NamedCompositeOperator {
+            fn apply(&self, manifold: &Manifold) -> UorResult<Manifold> {
+                self.run(manifold, &self.stages)
+            }
+
+            fn clone_box(&self) -> Option<Box<dyn HpcOperator>> {
+                let stages = self
+                    .stages
+                    .iter()
+                    .map(|(name, op)| Some((name.clone(), op.clone_box()?)))
+                    .collect::<Option<Vec<_>>>()?;
+                Some(Box::new(NamedCompositeOperator {
+                    stages,
+                    short_circuit_empty: self.short_circuit_empty,
+                }))
+            }
+
+            fn set_seed(&mut self, seed: u64) {
+                for (_, op) in &mut self.stages {
+                    op.set_seed(seed);
+                }
+            }
 
-        impl HpcOperator for ExampleOperator {
-            fn apply(&self, manifold: &Manifold) -> UorResult<Manifold> {
-                // HPC logic or concurrency logic to transform manifold
-                Ok(manifold.clone())
+            fn warmup(&mut self) -> UorResult<()> {
+                for (_, op) in &mut self.stages {
+                    op.warmup()?;
+                }
+                Ok(())
             }
         }
     }
@@ -355,23 +4802,170 @@ pub mod uor_framework {
     /// The concurrency module organizes tasks or subgraphs of the manifold
     /// into parallelizable chunks.
     pub mod concurrency {
-        use super::manifold::Manifold;
-        use super::UorResult;
+        use super::manifold::{Manifold, ManifoldNode};
+        use super::{UorResult, UorError};
+        use std::collections::HashMap;
+        use std::fmt;
+        use std::sync::Arc;
+        use std::thread;
+
+        /// What a [`Scheduler::schedule`] call did with a manifold.
+        #[derive(Debug, Clone, Default, PartialEq, Eq)]
+        pub struct ScheduleReport {
+            /// Number of tasks (nodes) scheduled.
+            pub task_count: usize,
+            /// Number of workers the tasks were spread over.
+            pub worker_count: usize,
+            /// The worker index each node ID was assigned to.
+            pub assignments: HashMap<String, usize>,
+        }
 
         /// Trait for concurrency scheduling.
         pub trait Scheduler {
-            fn schedule(&mut self, manifold: &Manifold) -> UorResult<()>;
+            fn schedule(&mut self, manifold: &Manifold) -> UorResult<ScheduleReport>;
+
+            /// Name of this scheduler used when describing a stack.
+            fn kind(&self) -> &'static str {
+                std::any::type_name::<Self>()
+            }
+
+            /// A boxed copy of this scheduler, or `None` (the default) if it
+            /// can't be copied.
+            fn clone_box(&self) -> Option<Box<dyn Scheduler>> {
+                None
+            }
+
+            /// Reseed any randomness the scheduler uses. The default does
+            /// nothing.
+            fn set_seed(&mut self, _seed: u64) {}
+
+            /// Do any expensive one-off setup (e.g. spawning a pool) ahead
+            /// of the first `schedule`. The default does nothing.
+            fn warmup(&mut self) -> UorResult<()> {
+                Ok(())
+            }
         }
 
-        /// Example round-robin scheduler for HPC tasks.
-        #[derive(Default)]
-        pub struct RoundRobinScheduler;
+        /// Work a [`RoundRobinScheduler`] worker runs on each node of its
+        /// bucket.
+        pub type NodeTask = Arc<dyn Fn(&ManifoldNode) + Send + Sync>;
+
+        /// Deals the manifold's nodes, in ID order, round-robin into one
+        /// bucket per worker and hands each bucket to its own thread, which
+        /// runs the scheduler's `task` (if any) on every node in it.
+        ///
+        /// The buckets and the number of nodes each worker handled are kept
+        /// from the latest `schedule` call. A worker that panics fails the
+        /// call with `UorError::ConcurrencyError`.
+        #[derive(Clone)]
+        pub struct RoundRobinScheduler {
+            /// Number of worker threads, at least 1.
+            pub workers: usize,
+            pub task: Option<NodeTask>,
+            buckets: Vec<Vec<String>>,
+            node_counts: Vec<usize>,
+        }
+
+        impl RoundRobinScheduler {
+            pub fn new(workers: usize) -> Self {
+                Self {
+                    workers,
+                    task: None,
+                    buckets: Vec::new(),
+                    node_counts: Vec::new(),
+                }
+            }
+
+            /// Set the work each worker runs on the nodes of its bucket.
+            pub fn with_task(mut self, task: impl Fn(&ManifoldNode) + Send + Sync + 'static) -> Self {
+                self.task = Some(Arc::new(task));
+                self
+            }
+
+            /// The node IDs dealt to each worker by the latest `schedule`.
+            pub fn buckets(&self) -> &[Vec<String>] {
+                &self.buckets
+            }
+
+            /// How many nodes each worker handled in the latest `schedule`.
+            pub fn node_counts(&self) -> &[usize] {
+                &self.node_counts
+            }
+        }
+
+        impl fmt::Debug for RoundRobinScheduler {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct("RoundRobinScheduler")
+                    .field("workers", &self.workers)
+                    .field("task", &self.task.as_ref().map(|_| "<fn>"))
+                    .field("buckets", &self.buckets)
+                    .field("node_counts", &self.node_counts)
+                    .finish()
+            }
+        }
+
+        /// One worker per available CPU.
+        impl Default for RoundRobinScheduler {
+            fn default() -> Self {
+                Self::new(thread::available_parallelism().map_or(1, |n| n.get()))
+            }
+        }
 
         impl Scheduler for RoundRobinScheduler {
-            fn schedule(&mut self, _manifold: &Manifold) -> UorResult<()> {
-                // In a real implementation, we would spawn tasks,
-                // distribute across threads, etc.
-                Ok(())
+            fn schedule(&mut self, manifold: &Manifold) -> UorResult<ScheduleReport> {
+                if self.workers == 0 {
+                    return Err(UorError::ConcurrencyError(
+                        "RoundRobinScheduler needs at least one worker".into(),
+                    ));
+                }
+                let mut buckets = vec![Vec::new(); self.workers];
+                for (i, id) in manifold.sorted_ids().into_iter().enumerate() {
+                    buckets[i % self.workers].push(id.to_string());
+                }
+
+                let task = self.task.as_deref();
+                let node_counts = thread::scope(|scope| {
+                    let handles: Vec<_> = buckets
+                        .iter()
+                        .map(|bucket| {
+                            scope.spawn(move || {
+                                for id in bucket {
+                                    if let Some(task) = task {
+                                        task(&manifold.nodes[id]);
+                                    }
+                                }
+                                bucket.len()
+                            })
+                        })
+                        .collect();
+                    handles
+                        .into_iter()
+                        .enumerate()
+                        .map(|(worker, handle)| {
+                            handle.join().map_err(|_| {
+                                UorError::ConcurrencyError(format!("Scheduler worker {} panicked", worker))
+                            })
+                        })
+                        .collect::<UorResult<Vec<usize>>>()
+                })?;
+
+                let assignments = buckets
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(worker, bucket)| bucket.iter().map(move |id| (id.clone(), worker)))
+                    .collect();
+                let report = ScheduleReport {
+                    task_count: node_counts.iter().sum(),
+                    worker_count: self.workers,
+                    assignments,
+                };
+                self.buckets = buckets;
+                self.node_counts = node_counts;
+                Ok(report)
+            }
+
+            fn clone_box(&self) -> Option<Box<dyn Scheduler>> {
+                Some(Box::new(self.clone()))
             }
         }
     }
@@ -384,13 +4978,17 @@ pub mod uor_framework {
     /// The cognitive_stack module implements the overarching “stack”
     /// that ties together multiple foundation models, concurrency, and operators.
     pub mod cognitive_stack {
-        use super::foundation_model::FoundationModel;
+        #[cfg(feature = "async")]
+        use super::foundation_model::AsyncFoundationModel;
+        use super::foundation_model::{FoundationModel, ModelMetrics, TryCloneModel};
         use super::manifold::Manifold;
         use super::cortex::MemoryCortex;
-        use super::embedding::{QuaternionEmbedding, DefaultQuaternionEmbedding};
+        use super::embedding::{Quaternion, QuaternionEmbedding, DefaultQuaternionEmbedding};
         use super::operators::{HpcOperator, ExampleOperator};
-        use super::concurrency::{Scheduler, RoundRobinScheduler};
-        use super::UorResult;
+        use super::concurrency::{Scheduler, RoundRobinScheduler, ScheduleReport};
+        use super::{UorResult, UorError};
+        use std::collections::HashMap;
+        use std::ops::ControlFlow;
 
         /// A container for multiple Foundation Models plus an optional kernel.
         ///
@@ -411,6 +5009,151 @@ pub mod uor_framework {
 
             // Memory space for this stack:
             pub cortex: MemoryCortex,
+
+            // Optional cap on the size of every intermediate manifold:
+            pub node_budget: Option<NodeBudget>,
+
+            // Handle of the model at each position of `models`:
+            model_handles: Vec<ModelHandle>,
+            next_model_handle: u64,
+
+            // Seed last passed to `set_seed`, applied to models added later:
+            seed: Option<u64>,
+
+            // What the scheduler reported on the latest run:
+            last_schedule_report: Option<ScheduleReport>,
+        }
+
+        /// The most nodes any stage of a stack may produce. Checked after
+        /// each model and after the operator; a stage that exceeds it fails
+        /// the run with `UorError::General`.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub struct NodeBudget {
+            pub max_nodes: usize,
+        }
+
+        impl NodeBudget {
+            pub fn new(max_nodes: usize) -> Self {
+                Self { max_nodes }
+            }
+
+            fn check(&self, stage: &str, manifold: &Manifold) -> UorResult<()> {
+                if manifold.nodes.len() > self.max_nodes {
+                    return Err(UorError::General(format!(
+                        "{} produced {} nodes, over the budget of {}",
+                        stage,
+                        manifold.nodes.len(),
+                        self.max_nodes
+                    )));
+                }
+                Ok(())
+            }
+        }
+
+        /// Most models a stack holds through [`CognitiveStack::add_model`].
+        pub const MAX_MODELS: usize = 12;
+
+        /// A stable reference to a model in a stack, from
+        /// [`CognitiveStack::add_model`].
+        ///
+        /// Unlike a position in `models`, a handle keeps referring to the
+        /// same model when others are added or removed, and never refers to
+        /// a different model after its own is removed.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct ModelHandle(u64);
+
+        /// A snapshot of a stack's configuration, from
+        /// [`CognitiveStack::describe`].
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct StackDescription {
+            pub model_count: usize,
+            pub model_names: Vec<String>,
+            /// Each model's [`FoundationModel::metrics`], in model order.
+            pub model_metrics: Vec<Option<ModelMetrics>>,
+            /// Number of prime reference points in the cortex.
+            pub cortex_size: usize,
+            pub embedding: String,
+            pub operator: String,
+            pub scheduler: String,
+        }
+
+        /// Assembles a [`CognitiveStack`] with chosen components; anything
+        /// not set gets the same default as [`CognitiveStack::new_default`].
+        pub struct CognitiveStackBuilder<M> {
+            models: Vec<M>,
+            embedding: Option<Box<dyn QuaternionEmbedding>>,
+            operator: Option<Box<dyn HpcOperator>>,
+            scheduler: Option<Box<dyn Scheduler>>,
+            cortex: Option<MemoryCortex>,
+        }
+
+        impl<M> Default for CognitiveStackBuilder<M> {
+            fn default() -> Self {
+                Self {
+                    models: Vec::new(),
+                    embedding: None,
+                    operator: None,
+                    scheduler: None,
+                    cortex: None,
+                }
+            }
+        }
+
+        impl<M> CognitiveStackBuilder<M> {
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            pub fn with_embedding(mut self, embedding: Box<dyn QuaternionEmbedding>) -> Self {
+                self.embedding = Some(embedding);
+                self
+            }
+
+            pub fn with_operator(mut self, operator: Box<dyn HpcOperator>) -> Self {
+                self.operator = Some(operator);
+                self
+            }
+
+            pub fn with_scheduler(mut self, scheduler: Box<dyn Scheduler>) -> Self {
+                self.scheduler = Some(scheduler);
+                self
+            }
+
+            pub fn with_cortex(mut self, cortex: MemoryCortex) -> Self {
+                self.cortex = Some(cortex);
+                self
+            }
+
+            /// Append a model; models run in the order they are added.
+            pub fn add_model(mut self, model: M) -> Self {
+                self.models.push(model);
+                self
+            }
+
+            /// Errors if more than [`MAX_MODELS`] models were added.
+            pub fn build(self) -> UorResult<CognitiveStack<M>> {
+                if self.models.len() > MAX_MODELS {
+                    return Err(UorError::General(format!(
+                        "A cognitive stack holds at most {} models, but {} were added",
+                        MAX_MODELS,
+                        self.models.len()
+                    )));
+                }
+                let mut stack = CognitiveStack::new_default(self.models);
+                if let Some(embedding) = self.embedding {
+                    stack.embedding = embedding;
+                }
+                if let Some(operator) = self.operator {
+                    stack.operator = operator;
+                }
+                if let Some(scheduler) = self.scheduler {
+                    stack.scheduler = scheduler;
+                }
+                if let Some(cortex) = self.cortex {
+                    stack.cortex = cortex;
+                }
+                Ok(stack)
+            }
         }
 
         impl<M> Default for CognitiveStack<M>
@@ -418,13 +5161,108 @@ pub mod uor_framework {
             M: FoundationModel + Default,
         {
             fn default() -> Self {
+                Self::new_default(vec![M::default()])
+            }
+        }
+
+        impl<M> CognitiveStack<M> {
+            /// Creates a new cognitive stack with user-provided models.
+            ///
+            /// The rest of the fields are initialized with default types.
+            pub fn new_default(models: Vec<M>) -> Self {
+                let count = models.len() as u64;
                 Self {
-                    models: vec![M::default()],
-                    embedding: Box::new(DefaultQuaternionEmbedding::default()),
-                    operator: Box::new(ExampleOperator::default()),
+                    models,
+                    embedding: Box::new(DefaultQuaternionEmbedding),
+                    operator: Box::new(ExampleOperator),
                     scheduler: Box::new(RoundRobinScheduler::default()),
                     cortex: MemoryCortex::default(),
+                    node_budget: None,
+                    model_handles: (0..count).map(ModelHandle).collect(),
+                    next_model_handle: count,
+                    seed: None,
+                    last_schedule_report: None,
+                }
+            }
+        }
+
+        #[cfg(feature = "async")]
+        impl<M> CognitiveStack<M>
+        where
+            M: AsyncFoundationModel
+        {
+            /// [`process`](Self::process) for async models: each model's
+            /// future is awaited in turn, then the scheduler, operator and
+            /// embedding run as usual. Preconditions and the node budget are
+            /// checked exactly as in the synchronous pipeline.
+            pub async fn process_async(&mut self, mut manifold: Manifold) -> UorResult<()> {
+                let hook = &mut |_: &str, _: &Manifold| ControlFlow::Continue(());
+                for i in 0..self.models.len() {
+                    let model = &mut self.models[i];
+                    reject_input(i, model.precondition(&manifold))?;
+                    manifold = model.process_manifold(&manifold).await?;
+                    if self.finish_model_stage(i, &manifold, hook)?.is_break() {
+                        return Ok(());
+                    }
+                }
+                if let ControlFlow::Continue(manifold) = self.run_operator_stages(manifold, hook)? {
+                    self.embedding.embed_manifold(&manifold, &mut self.cortex)?;
+                }
+                Ok(())
+            }
+        }
+
+        /// Prefix a failed precondition of model `i` with its stage.
+        fn reject_input(i: usize, result: UorResult<()>) -> UorResult<()> {
+            result.map_err(|e| e.with_context(&format!("model[{}] rejected its input", i)))
+        }
+
+        /// The parts of the pipeline that don't depend on the model type,
+        /// shared by the synchronous and async paths.
+        impl<M> CognitiveStack<M> {
+            /// What the scheduler reported the last time a run reached it,
+            /// or `None` before then.
+            pub fn last_schedule_report(&self) -> Option<&ScheduleReport> {
+                self.last_schedule_report.as_ref()
+            }
+
+            /// Check the node budget and consult `hook` once model `i` has
+            /// produced `manifold`.
+            fn finish_model_stage(
+                &self,
+                i: usize,
+                manifold: &Manifold,
+                hook: &mut dyn FnMut(&str, &Manifold) -> ControlFlow<()>,
+            ) -> UorResult<ControlFlow<()>> {
+                let stage = format!("model[{}]", i);
+                if let Some(budget) = self.node_budget {
+                    budget.check(&stage, manifold)?;
+                }
+                Ok(hook(&stage, manifold))
+            }
+
+            /// Steps 2–3, consulting `hook` after each stage. `Break` carries
+            /// the manifold where the hook stopped the pipeline.
+            fn run_operator_stages(
+                &mut self,
+                mut manifold: Manifold,
+                hook: &mut dyn FnMut(&str, &Manifold) -> ControlFlow<()>,
+            ) -> UorResult<ControlFlow<Manifold, Manifold>> {
+                // Step 2: Concurrency scheduling
+                self.last_schedule_report = Some(self.scheduler.schedule(&manifold)?);
+                if hook("scheduler", &manifold).is_break() {
+                    return Ok(ControlFlow::Break(manifold));
+                }
+
+                // Step 3: HPC operator transformations
+                manifold = self.operator.apply(&manifold)?;
+                if let Some(budget) = self.node_budget {
+                    budget.check("operator", &manifold)?;
+                }
+                if hook("operator", &manifold).is_break() {
+                    return Ok(ControlFlow::Break(manifold));
                 }
+                Ok(ControlFlow::Continue(manifold))
             }
         }
 
@@ -432,16 +5270,125 @@ pub mod uor_framework {
         where
             M: FoundationModel
         {
-            /// Creates a new cognitive stack with user-provided models.
+            /// Seed every component (models, embedding, operator and
+            /// scheduler) through their `set_seed` methods, so runs with the
+            /// same seed and input are reproducible. Models added afterwards
+            /// with [`add_model`](Self::add_model) are seeded too.
+            pub fn set_seed(&mut self, seed: u64) {
+                self.seed = Some(seed);
+                for model in &mut self.models {
+                    model.set_seed(seed);
+                }
+                self.embedding.set_seed(seed);
+                self.operator.set_seed(seed);
+                self.scheduler.set_seed(seed);
+            }
+
+            /// The seed last passed to [`set_seed`](Self::set_seed), if any.
+            pub fn seed(&self) -> Option<u64> {
+                self.seed
+            }
+
+            /// Run every component's `warmup` (the models in order, then the
+            /// embedding, operator and scheduler), so the first `process`
+            /// doesn't pay for one-off setup. Stops at the first error,
+            /// prefixed with the component that raised it.
+            pub fn warmup(&mut self) -> UorResult<()> {
+                for (i, model) in self.models.iter_mut().enumerate() {
+                    model.warmup().map_err(|e| e.with_context(&format!("model[{}] warmup failed", i)))?;
+                }
+                self.embedding.warmup().map_err(|e| e.with_context("embedding warmup failed"))?;
+                self.operator.warmup().map_err(|e| e.with_context("operator warmup failed"))?;
+                self.scheduler.warmup().map_err(|e| e.with_context("scheduler warmup failed"))?;
+                Ok(())
+            }
+
+            /// Append a model, returning a handle that stays valid until that
+            /// model is removed.
             ///
-            /// The rest of the fields are initialized with default types.
-            pub fn new_default(models: Vec<M>) -> Self {
-                Self {
+            /// Errors if the stack already holds [`MAX_MODELS`] models. Mixing
+            /// handles with direct edits to `models` is not supported.
+            pub fn add_model(&mut self, mut model: M) -> UorResult<ModelHandle> {
+                if self.models.len() >= MAX_MODELS {
+                    return Err(UorError::General(format!(
+                        "A cognitive stack holds at most {} models",
+                        MAX_MODELS
+                    )));
+                }
+                if let Some(seed) = self.seed {
+                    model.set_seed(seed);
+                }
+                let handle = ModelHandle(self.next_model_handle);
+                self.next_model_handle += 1;
+                self.models.push(model);
+                self.model_handles.push(handle);
+                Ok(handle)
+            }
+
+            /// The model behind `handle`, or `None` if it was removed.
+            pub fn get_model(&self, handle: ModelHandle) -> Option<&M> {
+                self.models.get(self.model_position(handle)?)
+            }
+
+            /// Remove and return the model behind `handle`, or `None` if it
+            /// was already removed. Other handles are unaffected.
+            pub fn remove_model(&mut self, handle: ModelHandle) -> Option<M> {
+                let position = self.model_position(handle)?;
+                self.model_handles.remove(position);
+                Some(self.models.remove(position))
+            }
+
+            /// Duplicate the stack, including its cortex, so the same
+            /// configuration can run on other inputs independently. Handles
+            /// from the original remain valid for the copy.
+            ///
+            /// Errors naming the first component that can't be copied (see
+            /// the `clone_box` methods on the component traits).
+            pub fn try_clone(&self) -> UorResult<Self>
+            where
+                M: TryCloneModel,
+            {
+                fn uncloneable(what: &str) -> UorError {
+                    UorError::General(format!("{} does not support cloning", what))
+                }
+
+                let models = self
+                    .models
+                    .iter()
+                    .map(|m| m.try_clone_model().ok_or_else(|| uncloneable(m.name())))
+                    .collect::<UorResult<Vec<M>>>()?;
+                Ok(Self {
                     models,
-                    embedding: Box::new(DefaultQuaternionEmbedding::default()),
-                    operator: Box::new(ExampleOperator::default()),
-                    scheduler: Box::new(RoundRobinScheduler::default()),
-                    cortex: MemoryCortex::default(),
+                    embedding: self.embedding.clone_box().ok_or_else(|| uncloneable(self.embedding.kind()))?,
+                    operator: self.operator.clone_box().ok_or_else(|| uncloneable(self.operator.kind()))?,
+                    scheduler: self.scheduler.clone_box().ok_or_else(|| uncloneable(self.scheduler.kind()))?,
+                    cortex: self.cortex.clone(),
+                    node_budget: self.node_budget,
+                    model_handles: self.model_handles.clone(),
+                    next_model_handle: self.next_model_handle,
+                    seed: self.seed,
+                    last_schedule_report: self.last_schedule_report.clone(),
+                })
+            }
+
+            fn model_position(&self, handle: ModelHandle) -> Option<usize> {
+                self.model_handles
+                    .iter()
+                    .position(|&h| h == handle)
+                    .filter(|&position| position < self.models.len())
+            }
+
+            /// Describe the stack's models and components, for tooling and
+            /// debugging.
+            pub fn describe(&self) -> StackDescription {
+                StackDescription {
+                    model_count: self.models.len(),
+                    model_names: self.models.iter().map(|m| m.name().to_string()).collect(),
+                    model_metrics: self.models.iter().map(|m| m.metrics()).collect(),
+                    cortex_size: self.cortex.references.len(),
+                    embedding: self.embedding.kind().to_string(),
+                    operator: self.operator.kind().to_string(),
+                    scheduler: self.scheduler.kind().to_string(),
                 }
             }
 
@@ -450,22 +5397,206 @@ pub mod uor_framework {
             /// 2) The concurrency scheduler is invoked.
             /// 3) The HPC operator is applied.
             /// 4) The manifold is embedded in quaternion space.
-            pub fn process(&mut self, mut manifold: Manifold) -> UorResult<()> {
+            ///
+            /// See [`process_traced`](Self::process_traced) to keep the
+            /// intermediate results.
+            pub fn process(&mut self, manifold: Manifold) -> UorResult<()> {
+                let manifold = self.run_stages(manifold)?;
+                // Step 4: Embedding
+                self.embedding.embed_manifold(&manifold, &mut self.cortex)?;
+                Ok(())
+            }
+
+            /// Process the manifold like [`process`](Self::process), keeping
+            /// a snapshot after each model and after the operator, plus the
+            /// embedding.
+            pub fn process_traced(&mut self, manifold: Manifold) -> UorResult<ProcessTrace> {
+                let mut model_outputs = Vec::with_capacity(self.models.len());
+                let mut record = |stage: &str, manifold: &Manifold| {
+                    if stage.starts_with("model[") {
+                        model_outputs.push(manifold.clone());
+                    }
+                    ControlFlow::Continue(())
+                };
+                let operator_output = match self.run_stages_with(manifold, &mut record)? {
+                    ControlFlow::Break(manifold) | ControlFlow::Continue(manifold) => manifold,
+                };
+
+                // Step 4: Embedding
+                let embedding = self.embedding.embed_manifold(&operator_output, &mut self.cortex)?;
+
+                Ok(ProcessTrace {
+                    model_outputs,
+                    operator_output,
+                    embedding,
+                })
+            }
+
+            /// Run the model, scheduler and operator stages once, then embed
+            /// the result with each of `embeddings` in turn.
+            ///
+            /// Returns one quaternion set per embedding, in order. The stack's
+            /// own `embedding` is not used.
+            pub fn process_multi_embedding(
+                &mut self,
+                manifold: Manifold,
+                embeddings: &[Box<dyn QuaternionEmbedding>],
+            ) -> UorResult<Vec<Vec<Quaternion>>> {
+                let manifold = self.run_stages(manifold)?;
+                embeddings
+                    .iter()
+                    .map(|embedding| embedding.embed_manifold(&manifold, &mut self.cortex))
+                    .collect()
+            }
+
+            /// Lazily process each manifold from `manifolds` as the returned
+            /// iterator is pulled, yielding its embedding.
+            ///
+            /// Nothing runs until the first item is requested, and an error
+            /// for one manifold doesn't stop the later ones. The stack's
+            /// cortex carries over from item to item, so embeddings can
+            /// accumulate state across the stream.
+            pub fn process_stream<'a, I>(
+                &'a mut self,
+                manifolds: I,
+            ) -> impl Iterator<Item = UorResult<Vec<Quaternion>>> + 'a
+            where
+                I: Iterator<Item = Manifold> + 'a,
+            {
+                manifolds.map(move |manifold| {
+                    let manifold = self.run_stages(manifold)?;
+                    self.embedding.embed_manifold(&manifold, &mut self.cortex)
+                })
+            }
+
+            /// Process the manifold like [`process`](Self::process), calling
+            /// `hook` with the stage name and current manifold after each
+            /// stage so it can stop the pipeline early.
+            ///
+            /// Stages are named `model[0]`, `model[1]`, ..., `scheduler` and
+            /// `operator`. If the hook returns `ControlFlow::Break`, no
+            /// further stage (including the embedding) runs and the manifold
+            /// as of that stage is returned. Otherwise the embedding runs and
+            /// the post-operator manifold is returned.
+            pub fn process_with_control(
+                &mut self,
+                manifold: Manifold,
+                mut hook: impl FnMut(&str, &Manifold) -> ControlFlow<()>,
+            ) -> UorResult<Manifold> {
+                match self.run_stages_with(manifold, &mut hook)? {
+                    ControlFlow::Break(partial) => Ok(partial),
+                    ControlFlow::Continue(manifold) => {
+                        self.embedding.embed_manifold(&manifold, &mut self.cortex)?;
+                        Ok(manifold)
+                    }
+                }
+            }
+
+            /// Steps 1–3 of the pipeline: models, scheduling, operator.
+            fn run_stages(&mut self, manifold: Manifold) -> UorResult<Manifold> {
+                match self.run_stages_with(manifold, &mut |_, _| ControlFlow::Continue(()))? {
+                    ControlFlow::Break(manifold) | ControlFlow::Continue(manifold) => Ok(manifold),
+                }
+            }
+
+            /// Steps 1–3, consulting `hook` after each stage. `Break` carries
+            /// the manifold where the hook stopped the pipeline.
+            fn run_stages_with(
+                &mut self,
+                mut manifold: Manifold,
+                hook: &mut dyn FnMut(&str, &Manifold) -> ControlFlow<()>,
+            ) -> UorResult<ControlFlow<Manifold, Manifold>> {
                 // Step 1: Pass through foundation models
-                for model in &mut self.models {
+                for i in 0..self.models.len() {
+                    let model = &mut self.models[i];
+                    reject_input(i, model.precondition(&manifold))?;
                     manifold = model.process_manifold(&manifold)?;
+                    if self.finish_model_stage(i, &manifold, hook)?.is_break() {
+                        return Ok(ControlFlow::Break(manifold));
+                    }
                 }
+                self.run_operator_stages(manifold, hook)
+            }
+        }
 
-                // Step 2: Concurrency scheduling
-                self.scheduler.schedule(&manifold)?;
+        /// The intermediate results of one run, from
+        /// [`CognitiveStack::process_traced`].
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct ProcessTrace {
+            /// The manifold after each model, in model order.
+            pub model_outputs: Vec<Manifold>,
+            /// The manifold after the HPC operator, as embedded.
+            pub operator_output: Manifold,
+            pub embedding: Vec<Quaternion>,
+        }
 
-                // Step 3: HPC operator transformations
-                manifold = self.operator.apply(&manifold)?;
+        /// Hit and miss counts for a [`CachingCognitiveStack`].
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+        pub struct CacheStats {
+            pub hits: usize,
+            pub misses: usize,
+        }
 
-                // Step 4: Embedding
-                let _quaternions = self.embedding.embed_manifold(&manifold, &mut self.cortex)?;
+        /// Wraps a [`CognitiveStack`] and memoizes its embedding output,
+        /// keyed by [`Manifold::structural_hash`] of the input manifold.
+        /// Each entry keeps its input, and a hit requires the new input to
+        /// equal it, so inputs whose hashes collide never share a result.
+        ///
+        /// A cache hit skips the whole pipeline, so models, scheduler and
+        /// cortex see only the first of several identical inputs. Borrowing
+        /// the stack mutably (to change its configuration) empties the cache.
+        pub struct CachingCognitiveStack<M> {
+            stack: CognitiveStack<M>,
+            cache: HashMap<u64, Vec<(Manifold, Vec<Quaternion>)>>,
+            stats: CacheStats,
+        }
 
-                Ok(())
+        impl<M> CachingCognitiveStack<M>
+        where
+            M: FoundationModel
+        {
+            pub fn new(stack: CognitiveStack<M>) -> Self {
+                Self {
+                    stack,
+                    cache: HashMap::new(),
+                    stats: CacheStats::default(),
+                }
+            }
+
+            pub fn stack(&self) -> &CognitiveStack<M> {
+                &self.stack
+            }
+
+            /// Mutable access to the wrapped stack. Clears the cache, since
+            /// any change may alter future results.
+            pub fn stack_mut(&mut self) -> &mut CognitiveStack<M> {
+                self.cache.clear();
+                &mut self.stack
+            }
+
+            pub fn stats(&self) -> CacheStats {
+                self.stats
+            }
+
+            /// Run the stack on `manifold` and return its embedding, reusing
+            /// a previous result for a structurally identical input.
+            pub fn process(&mut self, manifold: Manifold) -> UorResult<Vec<Quaternion>> {
+                let key = manifold.structural_hash();
+                let cached = self.cache.get(&key).and_then(|entries| {
+                    entries.iter().find(|(input, _)| *input == manifold)
+                });
+                if let Some((_, quaternions)) = cached {
+                    self.stats.hits += 1;
+                    return Ok(quaternions.clone());
+                }
+
+                self.stats.misses += 1;
+                let input = manifold.clone();
+                let manifold = self.stack.run_stages(manifold)?;
+                let stack = &mut self.stack;
+                let quaternions = stack.embedding.embed_manifold(&manifold, &mut stack.cortex)?;
+                self.cache.entry(key).or_default().push((input, quaternions.clone()));
+                Ok(quaternions)
             }
         }
     }
@@ -480,26 +5611,117 @@ pub mod uor_framework {
     /// coordinates and “learns” across different domain transformations.
     pub mod kernel {
         use super::foundation_model::FoundationModel;
-        use super::manifold::Manifold;
-        use super::UorResult;
+        use super::manifold::{parse_numeric, Manifold};
+        use super::{rng, UorResult};
 
         /// Example struct for a UOR Kernel NN.
-        #[derive(Default)]
+        ///
+        /// The kernel is a single unit. A node whose data is
+        /// `weights.len()` comma-separated numbers (its feature vector) has its
+        /// data replaced by `activation(weights · features + bias)`; other
+        /// nodes pass through. With no weights (the default) the kernel is the
+        /// identity.
+        #[derive(Debug, Clone, Default)]
         pub struct UorKernel {
-            // Hypothetical fields for neural network parameters, etc.
+            pub weights: Vec<f64>,
+            pub bias: f64,
+            pub activation: Activation,
+            /// The seed `weights` were drawn from, if they were random (see
+            /// [`UorKernel::with_seed`]). Hand-set weights have none and are
+            /// kept by `set_seed`.
+            pub seed: Option<u64>,
+        }
+
+        /// Nonlinearity applied to the kernel's linear output.
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+        pub enum Activation {
+            /// Leave the output linear.
+            #[default]
+            Identity,
+            /// `max(0, x)`.
+            Relu,
+            Tanh,
+            /// `1 / (1 + e^-x)`.
+            Sigmoid,
+        }
+
+        impl Activation {
+            pub fn apply(self, x: f64) -> f64 {
+                match self {
+                    Activation::Identity => x,
+                    Activation::Relu => x.max(0.0),
+                    Activation::Tanh => x.tanh(),
+                    Activation::Sigmoid => 1.0 / (1.0 + (-x).exp()),
+                }
+            }
         }
 
         impl UorKernel {
             pub fn new() -> Self {
                 Self::default()
             }
+
+            /// A kernel over `input_dim` features whose weights are drawn
+            /// deterministically from `seed`, uniformly in `[-0.5, 0.5)`.
+            pub fn with_seed(seed: u64, input_dim: usize) -> Self {
+                Self {
+                    weights: Self::random_weights(seed, input_dim),
+                    seed: Some(seed),
+                    ..Self::default()
+                }
+            }
+
+            fn random_weights(seed: u64, input_dim: usize) -> Vec<f64> {
+                let mut state = seed;
+                (0..input_dim)
+                    .map(|_| rng::unit_f64(&mut state) - 0.5)
+                    .collect()
+            }
+
+            /// Run the kernel over each manifold in turn, as repeated
+            /// `process_manifold` calls would. Stops at the first error.
+            pub fn process_batch(&mut self, manifolds: &[Manifold]) -> UorResult<Vec<Manifold>> {
+                manifolds.iter().map(|manifold| self.process_manifold(manifold)).collect()
+            }
+
+            /// Parse a node's data as exactly `weights.len()` features.
+            fn features(&self, data: &str) -> Option<Vec<f64>> {
+                let features: Vec<f64> = data.split(',').map(parse_numeric).collect::<Option<_>>()?;
+                (features.len() == self.weights.len()).then_some(features)
+            }
         }
 
         impl FoundationModel for UorKernel {
             fn process_manifold(&mut self, input: &Manifold) -> UorResult<Manifold> {
-                // Neural logic or advanced orchestration:
-                // For now, we simply return the input unchanged.
-                Ok(input.clone())
+                let mut output = input.clone();
+                if self.weights.is_empty() {
+                    return Ok(output);
+                }
+                for node in output.nodes.values_mut() {
+                    if let Some(features) = self.features(&node.data) {
+                        let linear: f64 = self
+                            .weights
+                            .iter()
+                            .zip(&features)
+                            .map(|(w, x)| w * x)
+                            .sum::<f64>()
+                            + self.bias;
+                        node.data = self.activation.apply(linear).to_string();
+                    }
+                }
+                Ok(output)
+            }
+
+            fn clone_box(&self) -> Option<Box<dyn FoundationModel>> {
+                Some(Box::new(self.clone()))
+            }
+
+            /// Redraws random weights from `seed`, keeping their count.
+            fn set_seed(&mut self, seed: u64) {
+                if self.seed.is_some() {
+                    self.weights = Self::random_weights(seed, self.weights.len());
+                    self.seed = Some(seed);
+                }
             }
         }
     }
@@ -508,15 +5730,33 @@ pub mod uor_framework {
     // 3. Top-Level Re-Exports
     // -----------------------------------------------------------------------
 
-    pub use chart::Chart;
-    pub use manifold::{Manifold, ManifoldNode};
-    pub use foundation_model::{FoundationModel, NullFoundationModel};
-    pub use cortex::{MemoryCortex, PrimeReference};
-    pub use embedding::{Quaternion, QuaternionEmbedding, DefaultQuaternionEmbedding};
-    pub use operators::{HpcOperator, ExampleOperator};
-    pub use concurrency::{Scheduler, RoundRobinScheduler};
-    pub use cognitive_stack::CognitiveStack;
-    pub use kernel::UorKernel;
+    pub use chart::{Chart, ChartFormat, ChartVisitor, CollectStrings};
+    pub use manifold::{
+        graph_metrics, parse_numeric, ComponentMetrics, DataEncoding, Edge, EditableManifold, GraphMetrics, Manifold,
+        ManifoldDiff, ManifoldNode, ManifoldNodeMut, MergePolicy, SerializeOptions, ALIGN_MIN_SIMILARITY,
+        MANIFOLD_FORMAT_VERSION, MAX_ISOMORPHISM_NODES,
+    };
+    pub use foundation_model::{
+        CountingFoundationModel, FoundationModel, ModelMetrics, NullFoundationModel, TimeBoundedModel,
+        TryCloneModel,
+    };
+    pub use cortex::{LruCortex, MemoryCortex, PrimeReference};
+    pub use embedding::{
+        embedding_distance, CliffordEmbedding, DefaultCliffordEmbedding, Multivector, Quaternion, QuaternionEmbedding,
+        DefaultQuaternionEmbedding, WeightedTopologicalEmbedding,
+    };
+    pub use operators::{
+        HpcOperator, ExampleOperator, CanonicalizeOperator, CollapseParallelOperator, ComponentLabelOperator,
+        CompositeOperator, CycleBreakerOperator,
+        DegreeFeatureOperator, DepthOperator, KMeansOperator, MaxDegreeOperator, NamedCompositeOperator,
+        NumericRelabelOperator, ObserverOperator, PageRankOperator, RelabelOperator, SchemaValidateOperator,
+    };
+    pub use concurrency::{NodeTask, Scheduler, RoundRobinScheduler, ScheduleReport};
+    pub use cognitive_stack::{
+        CacheStats, CachingCognitiveStack, CognitiveStack, CognitiveStackBuilder, ModelHandle, NodeBudget,
+        ProcessTrace, StackDescription, MAX_MODELS,
+    };
+    pub use kernel::{Activation, UorKernel};
 
     // -----------------------------------------------------------------------
     // 4. Example of Module Interdependence
@@ -528,8 +5768,8 @@ pub mod uor_framework {
     //   let chart = Chart::from_json("demo", "1.0", chart_data).unwrap();
     //   chart.parse().unwrap();
     //
-    //   // Convert chart to manifold (not implemented in this spec).
-    //   let manifold = Manifold::new();
+    //   // Convert chart to manifold (no `nodes` field, so it's empty).
+    //   let manifold = chart.to_manifold().unwrap();
     //
     //   // Build stack with one model + the kernel
     //   let mut stack = CognitiveStack::new_default(vec![UorKernel::new()]);
@@ -27,7 +27,6 @@ mod tests {
         assert_eq!(chart.version, "1.0");
         assert_eq!(chart.raw_json, json_data);
 
-        // Test parsing stub
         chart.parse().expect("Should parse chart without errors");
     }
 
@@ -37,6 +36,253 @@ mod tests {
         assert!(result.is_err(), "Empty JSON should fail to create chart");
     }
 
+    #[test]
+    fn test_chart_rejects_malformed_json_and_gets_fields() {
+        let json_data = r#"{"model":{"name":"base","layers":[{"size":3},{"size":5}]},"tags":["a","b"]}"#;
+        let chart = Chart::from_json("fields", "1.0", json_data).unwrap();
+        assert_eq!(chart.get_field("/model/name"), Some(&serde_json::json!("base")));
+        assert_eq!(chart.get_field("/model/layers/1/size"), Some(&serde_json::json!(5)));
+        assert_eq!(chart.get_field("/tags/0"), Some(&serde_json::json!("a")));
+        assert_eq!(chart.get_field("/tags/2"), None);
+        assert_eq!(chart.get_field("/model/missing"), None);
+
+        let truncated = Chart::from_json("truncated", "1.0", "{\"model\":{\n\"layers\":[1,");
+        match truncated {
+            Err(UorError::ChartError(message)) => {
+                assert!(message.contains("line 2"), "Unexpected message: {}", message)
+            }
+            other => panic!("Expected a chart error, got {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "templates")]
+    #[test]
+    fn test_chart_render_template_each() {
+        let template = r#"{"layers":[{{#each layers}}{{#unless @first}},{{/unless}}{"name":"{{name}}","size":{{size}}}{{/each}}]}"#;
+        let context = serde_json::json!({
+            "layers": [{"name": "in", "size": 3}, {"name": "say \"hi\"", "size": 5}]
+        });
+        let chart = Chart::render_template(template, &context, "rendered", "1.0").unwrap();
+        assert_eq!(chart.name, "rendered");
+        assert_eq!(chart.get_field("/layers/1/size"), Some(&serde_json::json!(5)));
+        assert_eq!(chart.get_field("/layers/1/name"), Some(&serde_json::json!("say \"hi\"")));
+
+        let broken = Chart::render_template(r#"{"size":{{size}}"#, &serde_json::json!({"size": 1}), "broken", "1.0");
+        assert!(matches!(broken, Err(UorError::ChartError(_))));
+    }
+
+    #[test]
+    fn test_chart_to_manifold() {
+        // The demo chart from the crate docs has no `nodes`.
+        let demo = Chart::from_json("demo", "1.0", r#"{"some":"json"}"#).unwrap();
+        let manifold = demo.to_manifold().unwrap();
+        assert!(manifold.nodes.is_empty() && manifold.edges.is_empty());
+
+        let chart = Chart::from_json(
+            "graph",
+            "1.0",
+            r#"{"nodes":[{"id":"A","data":"1","label":"ignored"},{"id":"B","data":2},{"id":"C"}],
+                "edges":[{"from":"A","to":"B"},{"from":"B","to":"C","weight":3}],
+                "extra":true}"#,
+        )
+        .unwrap();
+        let manifold = chart.to_manifold().unwrap();
+        assert_eq!(manifold.nodes["A"].data, "1");
+        assert_eq!(manifold.nodes["B"].data, "2");
+        assert_eq!(manifold.nodes["C"].data, "");
+        assert_eq!(manifold.topological_sort().unwrap(), ["A", "B", "C"]);
+
+        let dangling = Chart::from_json(
+            "dangling",
+            "1.0",
+            r#"{"nodes":[{"id":"A"}],"edges":[{"from":"A","to":"Z"}]}"#,
+        )
+        .unwrap();
+        assert!(matches!(dangling.to_manifold(), Err(UorError::ChartError(_))));
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_chart_from_yaml_matches_json() {
+        let json = r#"{"nodes":[{"id":"A","data":"1"},{"id":"B","data":2}],"edges":[{"from":"A","to":"B"}]}"#;
+        let yaml = "
+nodes:
+  - id: A
+    data: \"1\"
+  - id: B
+    data: 2
+edges:
+  - from: A
+    to: B
+";
+        let from_json = Chart::from_json("graph", "1.0", json).unwrap();
+        let from_yaml = Chart::from_yaml("graph", "1.0", yaml).unwrap();
+        assert_eq!(from_json.format(), ChartFormat::Json);
+        assert_eq!(from_yaml.format(), ChartFormat::Yaml);
+        assert_eq!(from_yaml.value(), from_json.value());
+        assert_eq!(from_yaml.to_manifold().unwrap(), from_json.to_manifold().unwrap());
+
+        assert!(matches!(Chart::from_yaml("empty", "1.0", "  \n"), Err(UorError::ChartError(_))));
+        assert!(matches!(Chart::from_yaml("bad", "1.0", "nodes: [unclosed"), Err(UorError::ChartError(_))));
+    }
+
+    #[test]
+    fn test_chart_get_pointer() {
+        let json_data = r#"{"model":{"layers":[{"size":3},{"size":5}]},"a/b":{"m~n":true}}"#;
+        let chart = Chart::from_json("pointers", "1.0", json_data).unwrap();
+
+        assert_eq!(chart.get_pointer("/model/layers/1/size").unwrap(), serde_json::json!(5));
+        // `~1` is an escaped `/` and `~0` an escaped `~`.
+        assert_eq!(chart.get_pointer("/a~1b/m~0n").unwrap(), serde_json::json!(true));
+        assert!(chart.get_pointer("").unwrap().is_object());
+
+        assert!(chart.get_pointer("/model/layers/2").is_err());
+        assert!(chart.get_pointer("/a/b").is_err());
+    }
+
+    #[test]
+    fn test_chart_to_pretty_json() {
+        let chart = Chart::from_json("pretty", "1.0", r#"{"b":[1,2],"a":{"c":true}}"#).unwrap();
+        let pretty = chart.to_pretty_json().unwrap();
+        assert_eq!(
+            pretty,
+            "{\n  \"a\": {\n    \"c\": true\n  },\n  \"b\": [\n    1,\n    2\n  ]\n}"
+        );
+    }
+
+    #[test]
+    fn test_chart_apply_patch() {
+        let chart = Chart::from_json(
+            "patched",
+            "1.0",
+            r#"{"model":{"layers":[{"size":3},{"size":5}],"name":"base"}}"#,
+        )
+        .unwrap();
+
+        let patched = chart
+            .apply_patch(
+                r#"[
+                    {"op":"test","path":"/model/name","value":"base"},
+                    {"op":"replace","path":"/model/layers/1/size","value":8},
+                    {"op":"add","path":"/model/layers/-","value":{"size":13}},
+                    {"op":"copy","from":"/model/name","path":"/model/parent"},
+                    {"op":"move","from":"/model/name","path":"/label"},
+                    {"op":"remove","path":"/model/layers/0"}
+                ]"#,
+            )
+            .expect("Patch should apply");
+        assert_eq!(patched.name, "patched");
+        assert_eq!(
+            patched.get_pointer("").unwrap(),
+            serde_json::json!({
+                "model": {"layers": [{"size": 8}, {"size": 13}], "parent": "base"},
+                "label": "base"
+            })
+        );
+
+        // A failing `test` aborts the whole patch.
+        let failed = chart.apply_patch(
+            r#"[{"op":"replace","path":"/model/name","value":"new"},
+                {"op":"test","path":"/model/name","value":"base"}]"#,
+        );
+        assert!(failed.is_err());
+        assert!(chart.apply_patch(r#"[{"op":"remove","path":"/missing"}]"#).is_err());
+        assert_eq!(chart.get_pointer("/model/name").unwrap(), serde_json::json!("base"));
+    }
+
+    #[test]
+    fn test_chart_redact() {
+        let chart = Chart::from_json(
+            "service",
+            "1.0",
+            r#"{"service":{"url":"https://example.org","auth":{"api_key":"s3cret","user":"bot"}},"tokens":["a","b"]}"#,
+        )
+        .unwrap();
+
+        let redacted = chart.redact(&["service.auth.api_key", "tokens.1"]).unwrap();
+        assert_eq!(
+            redacted.get_pointer("").unwrap(),
+            serde_json::json!({
+                "service": {"url": "https://example.org", "auth": {"api_key": "***", "user": "bot"}},
+                "tokens": ["a", "***"]
+            })
+        );
+        assert!(!redacted.raw_json.contains("s3cret"));
+        assert!(chart.raw_json.contains("s3cret"), "The original chart is unchanged");
+
+        assert!(chart.redact(&["service.auth.password"]).is_err());
+    }
+
+    #[test]
+    fn test_chart_infer_schema() {
+        let chart = Chart::from_json(
+            "profile",
+            "1.0",
+            r#"{"name": "ada", "age": 36, "score": 9.5, "tags": ["a", "b"], "mixed": [1, "x"]}"#,
+        )
+        .unwrap();
+        let schema: serde_json::Value = serde_json::from_str(&chart.infer_schema().unwrap()).unwrap();
+
+        assert_eq!(schema["type"], "object");
+        let properties = &schema["properties"];
+        assert_eq!(properties["name"]["type"], "string");
+        assert_eq!(properties["age"]["type"], "integer");
+        assert_eq!(properties["score"]["type"], "number");
+        assert_eq!(properties["tags"]["items"]["type"], "string");
+        assert_eq!(properties["mixed"]["items"]["anyOf"].as_array().unwrap().len(), 2);
+        assert_eq!(schema["required"].as_array().unwrap().len(), 5);
+    }
+
+    #[test]
+    fn test_chart_from_ndjson() {
+        let ndjson = "{\"id\": 1}\n\n{\"id\": 2}\n  \n{\"id\": 3}\n";
+        let charts = Chart::from_ndjson("config", ndjson).unwrap();
+        assert_eq!(charts.len(), 3);
+        assert_eq!(charts[2].name, "config[2]");
+        assert_eq!(charts[2].get_pointer("/id").unwrap(), 3);
+
+        let result = Chart::from_ndjson("config", "{\"id\": 1}\n\n{oops}\n");
+        assert!(matches!(result, Err(UorError::ChartError(msg)) if msg.contains("line 3")));
+    }
+
+    #[test]
+    fn test_chart_walk_visits_every_leaf() {
+        struct Leaves(Vec<String>);
+        impl ChartVisitor for Leaves {
+            fn visit(&mut self, pointer: &str, value: &serde_json::Value) -> UorResult<()> {
+                if !value.is_object() && !value.is_array() {
+                    self.0.push(pointer.to_string());
+                }
+                Ok(())
+            }
+        }
+
+        let chart = Chart::from_json(
+            "nested",
+            "1.0",
+            r#"{"model": {"name": "m", "layers": [4, "relu"]}, "a/b": true, "tag": "x"}"#,
+        )
+        .unwrap();
+
+        let mut leaves = Leaves(Vec::new());
+        chart.walk(&mut leaves).unwrap();
+        assert_eq!(leaves.0, vec!["/a~1b", "/model/layers/0", "/model/layers/1", "/model/name", "/tag"]);
+        for pointer in &leaves.0 {
+            assert!(chart.get_pointer(pointer).is_ok());
+        }
+
+        let mut strings = CollectStrings::default();
+        chart.walk(&mut strings).unwrap();
+        assert_eq!(
+            strings.strings,
+            vec![
+                ("/model/layers/1".to_string(), "relu".to_string()),
+                ("/model/name".to_string(), "m".to_string()),
+                ("/tag".to_string(), "x".to_string()),
+            ]
+        );
+    }
+
     // 2. Manifold Tests
     // ------------------
     #[test]
@@ -45,8 +291,8 @@ mod tests {
         assert_eq!(manifold.nodes.len(), 0);
         assert_eq!(manifold.edges.len(), 0);
 
-        let node_a = ManifoldNode { id: "A".to_string(), data: "DataA".to_string() };
-        let node_b = ManifoldNode { id: "B".to_string(), data: "DataB".to_string() };
+        let node_a = ManifoldNode::new("A", "DataA");
+        let node_b = ManifoldNode::new("B", "DataB");
 
         // Add nodes
         manifold.add_node(node_a);
@@ -58,7 +304,24 @@ mod tests {
         assert_eq!(manifold.edges.len(), 1);
         let adj = manifold.edges.get("A").unwrap();
         assert_eq!(adj.len(), 1);
-        assert_eq!(adj[0], "B");
+        assert_eq!(adj[0].to, "B");
+    }
+
+    #[test]
+    fn test_manifold_get_node_mut() {
+        let mut manifold = Manifold::new();
+        manifold.add_node(ManifoldNode::new("A", "before"));
+
+        {
+            let node = manifold.get_node_mut("A").expect("Node A exists");
+            assert_eq!(node.id(), "A");
+            *node.data = "after".into();
+            node.metadata.insert("edited".into(), "yes".into());
+        }
+        assert_eq!(manifold.nodes["A"].data, "after");
+        assert_eq!(manifold.nodes["A"].metadata["edited"], "yes");
+
+        assert!(manifold.get_node_mut("missing").is_err());
     }
 
     #[test]
@@ -69,6 +332,900 @@ mod tests {
         assert!(result.is_err(), "Adding an edge without valid nodes should fail");
     }
 
+    #[test]
+    fn test_manifold_add_edges_lenient() {
+        let mut manifold = Manifold::new();
+        manifold.add_node(ManifoldNode::new("A", ""));
+        manifold.add_node(ManifoldNode::new("B", ""));
+
+        let rejected = manifold.add_edges_lenient([
+            ("A", "B"),
+            ("A", "Missing"),
+            ("B", "A"),
+            ("Ghost", "B"),
+        ]);
+        assert_eq!(
+            rejected,
+            vec![
+                ("A".to_string(), "Missing".to_string()),
+                ("Ghost".to_string(), "B".to_string()),
+            ]
+        );
+        assert_eq!(manifold.edges["A"], [Edge::new("B")]);
+        assert_eq!(manifold.edges["B"], [Edge::new("A")]);
+        assert!(!manifold.edges.contains_key("Ghost"));
+    }
+
+    #[test]
+    fn test_manifold_iterates_neighborhoods() {
+        let mut manifold = Manifold::new();
+        for id in ["C", "A", "B"] {
+            manifold.add_node(ManifoldNode::new(id, &format!("Data{}", id)));
+        }
+        manifold.add_edge("A", "B").unwrap();
+        manifold.add_edge("A", "C").unwrap();
+        manifold.add_edge("B", "C").unwrap();
+
+        let mut pairs = Vec::new();
+        for (node, neighbors) in &manifold {
+            let ids: Vec<&str> = neighbors.iter().map(|n| n.id.as_str()).collect();
+            pairs.push((node.id.as_str(), ids));
+        }
+        assert_eq!(
+            pairs,
+            vec![("A", vec!["B", "C"]), ("B", vec!["C"]), ("C", vec![])]
+        );
+    }
+
+    #[test]
+    fn test_manifold_filter_edges() {
+        let mut manifold = Manifold::new();
+        for id in ["A", "B", "C"] {
+            manifold.add_node(ManifoldNode::new(id, ""));
+        }
+        let labels = std::collections::HashMap::from([
+            (("A", "B"), "derives"),
+            (("A", "C"), "mentions"),
+            (("B", "C"), "derives"),
+        ]);
+        for (from, to) in labels.keys() {
+            manifold.add_edge(from, to).unwrap();
+        }
+
+        let derived = manifold.filter_edges(|from, to| labels.get(&(from, to)) == Some(&"derives"));
+        assert_eq!(derived.nodes.len(), 3);
+        assert_eq!(derived.edges["A"], [Edge::new("B")]);
+        assert_eq!(derived.edges["B"], [Edge::new("C")]);
+
+        // The source manifold keeps all of its edges.
+        assert_eq!(manifold.edges["A"].len(), 2);
+    }
+
+    #[test]
+    fn test_editable_manifold_undo_redo() {
+        let mut original = Manifold::new();
+        for id in ["A", "B", "C"] {
+            original.add_node(ManifoldNode::new(id, id));
+        }
+        original.add_edge("A", "B").unwrap();
+        original.add_edge("B", "C").unwrap();
+        original.add_edge("C", "A").unwrap();
+
+        let mut editor = EditableManifold::new(original.clone());
+        editor.add_node(ManifoldNode::new("D", "new"));
+        editor.remove_edge("B", "C").unwrap();
+        editor.remove_node("A").unwrap();
+        assert_eq!(editor.manifold().nodes.len(), 3);
+        assert!(!editor.manifold().edges.contains_key("C"), "C -> A went with A");
+
+        assert!(editor.undo());
+        assert!(editor.undo());
+        assert!(editor.undo());
+        assert_eq!(*editor.manifold(), original);
+        assert!(!editor.undo(), "Nothing left to undo");
+
+        // Redo replays all three edits.
+        assert!(editor.redo().unwrap());
+        assert!(editor.redo().unwrap());
+        assert!(editor.redo().unwrap());
+        assert!(!editor.redo().unwrap());
+        assert!(editor.manifold().nodes.contains_key("D"));
+        assert!(!editor.manifold().nodes.contains_key("A"));
+
+        assert!(editor.remove_edge("B", "C").is_err());
+        assert!(editor.remove_node("A").is_err());
+    }
+
+    #[test]
+    fn test_editable_manifold_skips_duplicate_edge_on_simple_manifold() {
+        let mut manifold = Manifold { simple: true, ..Manifold::new() };
+        manifold.add_node(ManifoldNode::new("A", ""));
+        manifold.add_node(ManifoldNode::new("B", ""));
+        manifold.add_weighted_edge("A", "B", 2.0).unwrap();
+
+        let mut editor = EditableManifold::new(manifold.clone());
+        editor.add_edge("A", "B").unwrap();
+        assert!(!editor.undo(), "The duplicate add changed nothing");
+        assert_eq!(*editor.manifold(), manifold);
+        assert_eq!(editor.manifold().edges["A"], [Edge::weighted("B", 2.0)]);
+    }
+
+    #[test]
+    fn test_manifold_diff_after_operator() {
+        // An operator that grows the graph by one node and one edge.
+        struct GrowOperator;
+        impl HpcOperator for GrowOperator {
+            fn apply(&self, manifold: &Manifold) -> UorResult<Manifold> {
+                let mut out = manifold.clone();
+                out.add_node(ManifoldNode::new("C", "DataC"));
+                out.add_edge("B", "C")?;
+                Ok(out)
+            }
+        }
+
+        let mut before = Manifold::new();
+        before.add_node(ManifoldNode::new("A", "DataA"));
+        before.add_node(ManifoldNode::new("B", "DataB"));
+        before.add_edge("A", "B").unwrap();
+
+        let after = GrowOperator.apply(&before).unwrap();
+        let diff = before.diff(&after);
+        assert_eq!(diff.added_nodes, vec!["C".to_string()]);
+        assert!(diff.removed_nodes.is_empty());
+        assert!(diff.changed_nodes.is_empty());
+        assert_eq!(diff.added_edges, vec![("B".to_string(), "C".to_string())]);
+        assert!(diff.removed_edges.is_empty());
+
+        // The reverse direction reports the same changes as removals.
+        let reverse = after.diff(&before);
+        assert_eq!(reverse.removed_nodes, vec!["C".to_string()]);
+        assert_eq!(reverse.removed_edges, vec![("B".to_string(), "C".to_string())]);
+        assert!(before.diff(&before).is_empty());
+    }
+
+    #[test]
+    fn test_manifold_condensation() {
+        // A <-> B form a cycle, C -> D -> C another, with B -> C joining them.
+        let mut manifold = Manifold::new();
+        for id in ["A", "B", "C", "D", "E"] {
+            manifold.add_node(ManifoldNode::new(id, ""));
+        }
+        for (from, to) in [("A", "B"), ("B", "A"), ("B", "C"), ("C", "D"), ("D", "C"), ("D", "E")] {
+            manifold.add_edge(from, to).unwrap();
+        }
+        assert!(!manifold.is_acyclic());
+
+        let condensed = manifold.condensation();
+        assert!(condensed.is_acyclic());
+        assert_eq!(condensed.nodes.len(), 3);
+        assert_eq!(condensed.nodes["A,B"].data, "2");
+        assert_eq!(condensed.nodes["C,D"].data, "2");
+        assert_eq!(condensed.nodes["E"].data, "1");
+        assert_eq!(condensed.edges["A,B"], [Edge::new("C,D")]);
+        assert_eq!(condensed.edges["C,D"], [Edge::new("E")]);
+    }
+
+    #[test]
+    fn test_manifold_multi_source_bfs() {
+        // A -> B -> C -> D -> E, and X -> D.
+        let mut manifold = Manifold::new();
+        for id in ["A", "B", "C", "D", "E", "X", "Island"] {
+            manifold.add_node(ManifoldNode::new(id, ""));
+        }
+        for (from, to) in [("A", "B"), ("B", "C"), ("C", "D"), ("D", "E"), ("X", "D")] {
+            manifold.add_edge(from, to).unwrap();
+        }
+
+        let distance = manifold.multi_source_bfs(&["A", "X"]).unwrap();
+        assert_eq!(distance["A"], 0);
+        assert_eq!(distance["X"], 0);
+        assert_eq!(distance["C"], 2);
+        assert_eq!(distance["D"], 1, "D is 3 hops from A but 1 from X");
+        assert_eq!(distance["E"], 2);
+        assert!(!distance.contains_key("Island"));
+
+        assert!(manifold.multi_source_bfs(&["A", "Missing"]).is_err());
+    }
+
+    #[test]
+    fn test_manifold_longest_path() {
+        // Short branch A -> E, long branch A -> B -> C -> D -> E.
+        let mut manifold = Manifold::new();
+        for id in ["A", "B", "C", "D", "E"] {
+            manifold.add_node(ManifoldNode::new(id, ""));
+        }
+        for (from, to) in [("A", "E"), ("A", "B"), ("B", "C"), ("C", "D"), ("D", "E")] {
+            manifold.add_edge(from, to).unwrap();
+        }
+        assert_eq!(manifold.longest_path().unwrap(), vec!["A", "B", "C", "D", "E"]);
+
+        // A heavy shortcut outweighs the branch with more hops.
+        manifold.set_edge_weight("A", "E", 10.0).unwrap();
+        assert_eq!(manifold.longest_path().unwrap(), vec!["A", "E"]);
+
+        assert!(Manifold::new().longest_path().unwrap().is_empty());
+
+        manifold.add_edge("E", "A").unwrap();
+        assert!(manifold.longest_path().is_err(), "Cycles have no longest path");
+    }
+
+    #[test]
+    fn test_manifold_json_round_trip() {
+        let mut manifold = Manifold::new();
+        manifold.add_node(ManifoldNode::new("A", "1"));
+        manifold.add_node(ManifoldNode::new("B", "2"));
+        manifold.get_node_mut("A").unwrap().metadata.insert("cluster".into(), "0".into());
+        manifold.add_edge("A", "B").unwrap();
+        manifold.set_edge_weight("A", "B", 2.5).unwrap();
+        manifold.metadata.insert("source".into(), "test".into());
+
+        let json = manifold.to_json().unwrap();
+        assert!(json.contains(&format!("\"format_version\":{}", MANIFOLD_FORMAT_VERSION)));
+        assert_eq!(Manifold::from_json(&json).unwrap(), manifold);
+
+        // Documents from before versioning are upgraded.
+        let legacy = r#"{"nodes":[{"id":"A","data":"1"}],"edges":{}}"#;
+        assert_eq!(Manifold::from_json(legacy).unwrap().nodes["A"].data, "1");
+
+        // So are version 1 documents, which kept weights apart from edges.
+        let v1 = r#"{"format_version":1,"nodes":[{"id":"A","data":"1"},{"id":"B","data":"2"}],
+            "edges":{"A":["B","B"]},"edge_weights":{"A":{"B":2.5}}}"#;
+        let upgraded = Manifold::from_json(v1).unwrap();
+        assert_eq!(upgraded.edges["A"], [Edge::weighted("B", 2.5), Edge::weighted("B", 2.5)]);
+        assert!(!upgraded.simple && !upgraded.strict_dag);
+
+        // The strict_dag and simple flags survive a round trip, even though
+        // equality ignores them.
+        let flagged = Manifold { strict_dag: true, simple: true, ..manifold };
+        let restored = Manifold::from_json(&flagged.to_json().unwrap()).unwrap();
+        assert!(restored.strict_dag && restored.simple);
+        let restored: Manifold = serde_json::from_str(&serde_json::to_string(&flagged).unwrap()).unwrap();
+        assert!(restored.strict_dag && restored.simple);
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn test_manifold_bincode_round_trip() {
+        let mut manifold = Manifold::new();
+        for i in 0..50 {
+            manifold.add_node(ManifoldNode::new(&format!("node-{}", i), &i.to_string()));
+        }
+        for i in 1..50 {
+            manifold.add_edge(&format!("node-{}", i - 1), &format!("node-{}", i)).unwrap();
+        }
+
+        let bytes = manifold.to_bincode().unwrap();
+        assert_eq!(Manifold::from_bincode(&bytes).unwrap(), manifold);
+        assert!(bytes.len() < manifold.to_json().unwrap().len());
+        assert!(Manifold::from_bincode(&bytes[..bytes.len() / 2]).is_err());
+    }
+
+    #[cfg(feature = "petgraph")]
+    #[test]
+    fn test_manifold_petgraph_round_trip() {
+        let mut manifold = Manifold::new();
+        for (id, data) in [("A", "alpha"), ("B", "beta"), ("C", "gamma")] {
+            manifold.add_node(ManifoldNode::new(id, data));
+        }
+        manifold.add_edge("A", "B").unwrap();
+        manifold.add_edge("A", "B").unwrap();
+        manifold.add_edge("B", "C").unwrap();
+
+        let (graph, indices) = manifold.to_petgraph();
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(graph.edge_count(), 3);
+        assert_eq!(graph[indices["B"]], "beta");
+        assert!(!petgraph::algo::is_cyclic_directed(&graph));
+
+        assert_eq!(Manifold::from_petgraph(&graph, &indices).unwrap(), manifold);
+
+        let mut unnamed = indices.clone();
+        unnamed.remove("C");
+        assert!(Manifold::from_petgraph(&graph, &unnamed).is_err());
+    }
+
+    #[test]
+    fn test_manifold_serde_round_trip() {
+        // Manifolds are usable as fields of other serde types.
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Saved {
+            label: String,
+            graph: Manifold,
+        }
+
+        let mut manifold = Manifold::new();
+        for (id, data) in [("A", "1"), ("B", "2"), ("C", "3"), ("D", "text")] {
+            manifold.add_node(ManifoldNode::new(id, data));
+        }
+        manifold.add_edge("A", "B").unwrap();
+        manifold.add_edge("A", "C").unwrap();
+        manifold.add_edge("B", "D").unwrap();
+        manifold.add_edge("C", "D").unwrap();
+        manifold.add_edge("C", "D").unwrap();
+
+        let saved = Saved { label: "run-1".into(), graph: manifold.clone() };
+        let json = serde_json::to_string(&saved).unwrap();
+        let loaded: Saved = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.label, "run-1");
+        assert_eq!(loaded.graph, manifold);
+
+        let dangling = serde_json::json!({
+            "format_version": MANIFOLD_FORMAT_VERSION,
+            "nodes": [{"id": "A", "data": ""}],
+            "edges": {"A": [{"to": "B", "weight": 1.0}]}
+        });
+        let error = serde_json::from_value::<Manifold>(dangling).unwrap_err();
+        assert!(error.to_string().contains("not found"), "Unexpected error: {}", error);
+    }
+
+    #[test]
+    fn test_manifold_json_rejects_unknown_version() {
+        let future = r#"{"format_version":99,"nodes":[]}"#;
+        let result = Manifold::from_json(future);
+        assert!(matches!(result, Err(UorError::General(msg)) if msg.contains("format_version 99")));
+
+        let dangling = format!(
+            r#"{{"format_version":{},"nodes":[{{"id":"A","data":""}}],"edges":{{"A":[{{"to":"B","weight":1.0}}]}}}}"#,
+            MANIFOLD_FORMAT_VERSION
+        );
+        assert!(Manifold::from_json(&dangling).is_err());
+    }
+
+    #[test]
+    fn test_data_histogram() {
+        let mut manifold = Manifold::new();
+        for (id, data) in [("A", "dup"), ("B", "dup"), ("C", "dup"), ("D", "x"), ("E", "y")] {
+            manifold.add_node(ManifoldNode::new(id, data));
+        }
+        let histogram = manifold.data_histogram();
+        assert_eq!(histogram.len(), 3);
+        assert_eq!(histogram["dup"], 3);
+        assert_eq!(histogram["x"], 1);
+        assert_eq!(histogram["y"], 1);
+    }
+
+    #[test]
+    fn test_add_and_remove_biedge() {
+        let mut manifold = Manifold::new();
+        manifold.add_node(ManifoldNode::new("A", ""));
+        manifold.add_node(ManifoldNode::new("B", ""));
+
+        manifold.add_biedge("A", "B").unwrap();
+        manifold.add_biedge("B", "A").unwrap();
+        assert_eq!(manifold.edges["A"], [Edge::new("B"), Edge::new("B")]);
+        assert_eq!(manifold.edges["B"], [Edge::new("A"), Edge::new("A")]);
+        assert!(manifold.add_biedge("A", "missing").is_err());
+
+        manifold.remove_biedge("A", "B");
+        assert!(manifold.edges.is_empty());
+        manifold.remove_biedge("A", "B");
+        assert_eq!(manifold.nodes.len(), 2);
+    }
+
+    #[test]
+    fn test_removed_edges_take_their_weights() {
+        let mut manifold = Manifold::new();
+        manifold.add_node(ManifoldNode::new("A", ""));
+        manifold.add_node(ManifoldNode::new("B", ""));
+        manifold.add_biedge("A", "B").unwrap();
+        manifold.set_edge_weight("A", "B", 3.0).unwrap();
+
+        manifold.remove_biedge("A", "B");
+        manifold.add_biedge("A", "B").unwrap();
+        assert_eq!(manifold.edge_weight("A", "B"), 1.0);
+
+        manifold.set_edge_weight("A", "B", 3.0).unwrap();
+        let mut filtered = manifold.filter_edges(|from, _| from != "A");
+        filtered.add_edge("A", "B").unwrap();
+        assert_eq!(filtered.edge_weight("A", "B"), 1.0);
+
+        let mut editor = EditableManifold::new(manifold.clone());
+        editor.remove_edge("A", "B").unwrap();
+        editor.add_edge("A", "B").unwrap();
+        assert_eq!(editor.manifold().edge_weight("A", "B"), 1.0);
+        let mut editor = EditableManifold::new(manifold);
+        editor.remove_node("B").unwrap();
+        editor.add_node(ManifoldNode::new("B", ""));
+        editor.add_edge("A", "B").unwrap();
+        assert_eq!(editor.manifold().edge_weight("A", "B"), 1.0);
+    }
+
+    #[test]
+    fn test_simple_manifold_ignores_repeated_edges() {
+        let mut manifold = Manifold { simple: true, ..Manifold::new() };
+        manifold.add_node(ManifoldNode::new("A", ""));
+        manifold.add_node(ManifoldNode::new("B", ""));
+        manifold.add_weighted_edge("A", "B", 2.0).unwrap();
+        manifold.add_edge("A", "B").unwrap();
+        assert_eq!(manifold.edges["A"], [Edge::weighted("B", 2.0)]);
+
+        manifold.add_biedge("A", "B").unwrap();
+        manifold.add_biedge("A", "B").unwrap();
+        assert_eq!(manifold.edges["A"].len(), 1);
+        assert_eq!(manifold.edges["B"], [Edge::new("A")]);
+    }
+
+    #[test]
+    fn test_graph_metrics_path_and_components() {
+        let mut manifold = Manifold::new();
+        for id in ["A", "B", "C", "D", "E", "X", "Y"] {
+            manifold.add_node(ManifoldNode::new(id, ""));
+        }
+        // A path of length 4 plus a separate two-node component.
+        for (from, to) in [("A", "B"), ("B", "C"), ("C", "D"), ("D", "E"), ("X", "Y")] {
+            manifold.add_edge(from, to).unwrap();
+        }
+
+        let metrics = graph_metrics(&manifold);
+        assert_eq!(metrics.components.len(), 2);
+        let path = &metrics.components[0];
+        assert_eq!(path.nodes, vec!["A", "B", "C", "D", "E"]);
+        assert_eq!(path.diameter, 4);
+        assert_eq!(path.radius, 2);
+        assert_eq!(metrics.components[1].diameter, 1);
+        assert_eq!(metrics.diameter(), 4);
+    }
+
+    #[test]
+    fn test_isomorphism_ignores_labels() {
+        let build = |ids: [&str; 4], data: [&str; 4]| {
+            let mut manifold = Manifold::new();
+            for (id, data) in ids.iter().zip(data) {
+                manifold.add_node(ManifoldNode::new(id, data));
+            }
+            // A diamond: 0 -> 1, 0 -> 2, 1 -> 3, 2 -> 3.
+            for (from, to) in [(0, 1), (0, 2), (1, 3), (2, 3)] {
+                manifold.add_edge(ids[from], ids[to]).unwrap();
+            }
+            manifold
+        };
+        let left = build(["a", "b", "c", "d"], ["src", "x", "y", "sink"]);
+        let right = build(["w", "z", "y", "x"], ["src", "y", "x", "sink"]);
+        assert!(left.is_isomorphic_to(&right));
+        assert!(left.is_isomorphic_with_data(&right));
+
+        let relabeled_data = build(["w", "z", "y", "x"], ["sink", "y", "x", "src"]);
+        assert!(left.is_isomorphic_to(&relabeled_data));
+        assert!(!left.is_isomorphic_with_data(&relabeled_data));
+
+        // Reversing one edge breaks the shape.
+        let mut reversed = right.clone();
+        reversed.edges.get_mut("y").unwrap().retain(|edge| edge.to != "x");
+        reversed.add_edge("x", "y").unwrap();
+        assert!(!left.is_isomorphic_to(&reversed));
+
+        let mut large = Manifold::new();
+        for i in 0..=MAX_ISOMORPHISM_NODES {
+            large.add_node(ManifoldNode::new(&i.to_string(), ""));
+        }
+        assert!(!large.is_isomorphic_to(&large.clone()));
+    }
+
+    #[test]
+    fn test_edit_distance_upper_bound() {
+        let mut before = Manifold::new();
+        for id in ["A", "B", "C"] {
+            before.add_node(ManifoldNode::new(id, ""));
+        }
+        before.add_edge("A", "B").unwrap();
+        before.add_edge("B", "C").unwrap();
+
+        // One extra node and one extra edge (not touching it).
+        let mut after = before.clone();
+        after.add_node(ManifoldNode::new("D", ""));
+        after.add_edge("A", "C").unwrap();
+
+        assert_eq!(before.edit_distance_upper_bound(&before), 0);
+        assert_eq!(before.edit_distance_upper_bound(&after), 2);
+        assert_eq!(after.edit_distance_upper_bound(&before), 2);
+    }
+
+    fn strict_chain(ids: &[&str]) -> Manifold {
+        let mut manifold = Manifold::new();
+        manifold.strict_dag = true;
+        for id in ids {
+            manifold.add_node(ManifoldNode::new(id, ""));
+        }
+        for pair in ids.windows(2) {
+            manifold.add_edge(pair[0], pair[1]).unwrap();
+        }
+        manifold
+    }
+
+    #[test]
+    fn test_strict_dag_rejects_self_loop() {
+        let mut manifold = strict_chain(&["A"]);
+        assert!(manifold.would_create_cycle("A", "A"));
+        assert!(manifold.add_edge("A", "A").is_err());
+    }
+
+    #[test]
+    fn test_strict_dag_rejects_two_node_cycle() {
+        let mut manifold = strict_chain(&["A", "B"]);
+        assert!(manifold.add_edge("B", "A").is_err());
+        assert!(manifold.add_biedge("A", "B").is_err());
+        assert_eq!(manifold.edges["A"], [Edge::new("B")]);
+        assert!(!manifold.edges.contains_key("B"));
+    }
+
+    #[test]
+    fn test_strict_dag_rejects_longer_cycle() {
+        let mut manifold = strict_chain(&["A", "B", "C", "D"]);
+        assert!(manifold.would_create_cycle("D", "A"));
+        assert!(manifold.add_edge("D", "A").is_err());
+        assert!(manifold.add_edge("C", "B").is_err());
+
+        // Without the flag the same edge is accepted.
+        manifold.strict_dag = false;
+        manifold.add_edge("D", "A").unwrap();
+        assert!(!manifold.is_acyclic());
+    }
+
+    #[test]
+    fn test_strict_dag_accepts_valid_edges() {
+        let mut manifold = strict_chain(&["A", "B", "C"]);
+        assert!(!manifold.would_create_cycle("A", "C"));
+        manifold.add_edge("A", "C").unwrap();
+        manifold.add_edge("A", "C").unwrap();
+        assert!(manifold.is_acyclic());
+    }
+
+    #[test]
+    fn test_topological_generations_on_diamond() {
+        let mut manifold = Manifold::new();
+        for id in ["top", "left", "right", "bottom"] {
+            manifold.add_node(ManifoldNode::new(id, ""));
+        }
+        for (from, to) in [("top", "left"), ("top", "right"), ("left", "bottom"), ("right", "bottom")] {
+            manifold.add_edge(from, to).unwrap();
+        }
+
+        let generations = manifold.topological_generations().unwrap();
+        assert_eq!(generations, vec![vec!["top"], vec!["left", "right"], vec!["bottom"]]);
+
+        manifold.add_edge("bottom", "top").unwrap();
+        assert!(manifold.topological_generations().is_err());
+        assert!(Manifold::new().topological_generations().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_topological_sort() {
+        let mut manifold = Manifold::new();
+        for id in ["d", "c", "b", "a"] {
+            manifold.add_node(ManifoldNode::new(id, ""));
+        }
+        manifold.add_edge("c", "a").unwrap();
+        manifold.add_edge("d", "b").unwrap();
+
+        // Ready nodes are taken in ID order: c and d start, a frees after c.
+        assert_eq!(manifold.topological_sort().unwrap(), vec!["c", "a", "d", "b"]);
+
+        manifold.add_edge("a", "c").unwrap();
+        let result = manifold.topological_sort();
+        assert!(matches!(result, Err(UorError::General(msg)) if msg.ends_with("cycle through: a, c")));
+    }
+
+    #[test]
+    fn test_remove_node_prunes_edges() {
+        let mut manifold = Manifold::new();
+        for id in ["A", "B", "C"] {
+            manifold.add_node(ManifoldNode::new(id, ""));
+        }
+        manifold.add_edge("A", "B").unwrap();
+        manifold.add_edge("B", "C").unwrap();
+        manifold.add_edge("A", "C").unwrap();
+        manifold.add_edge("C", "B").unwrap();
+        manifold.set_edge_weight("A", "B", 2.0).unwrap();
+
+        manifold.remove_node("B").unwrap();
+        assert!(!manifold.nodes.contains_key("B"));
+        assert!(!manifold.edges.contains_key("B"));
+        assert!(manifold.edges.values().all(|targets| !targets.iter().any(|edge| edge.to == "B")));
+        assert_eq!(manifold.edges["A"], [Edge::new("C")]);
+        assert!(!manifold.edges.contains_key("C"));
+
+        assert!(manifold.remove_node("B").is_err());
+        assert!(manifold.remove_edge("A", "B").is_err());
+        manifold.remove_edge("A", "C").unwrap();
+        assert!(manifold.edges.is_empty());
+        assert!(manifold.remove_edge("A", "C").is_err());
+    }
+
+    #[test]
+    fn test_subgraph_where_numeric_nodes() {
+        let mut manifold = Manifold::new();
+        manifold.add_node(ManifoldNode::new("one", "1"));
+        manifold.add_node(ManifoldNode::new("label", "hello"));
+        manifold.add_node(ManifoldNode::new("two", "2.5"));
+        manifold.add_node(ManifoldNode::new("three", "3"));
+        manifold.add_edge("one", "two").unwrap();
+        manifold.add_edge("one", "label").unwrap();
+        manifold.add_edge("label", "three").unwrap();
+        manifold.add_edge("two", "three").unwrap();
+        manifold.set_edge_weight("two", "three", 4.0).unwrap();
+
+        let numeric = manifold.subgraph_where(|node| node.data.parse::<f64>().is_ok());
+        let mut ids: Vec<&String> = numeric.nodes.keys().collect();
+        ids.sort();
+        assert_eq!(ids, ["one", "three", "two"]);
+        assert_eq!(numeric.edges["one"], [Edge::new("two")]);
+        assert_eq!(numeric.edges["two"], [Edge::weighted("three", 4.0)]);
+        assert!(!numeric.edges.contains_key("label"));
+        assert_eq!(manifold.nodes.len(), 4, "The source manifold is unchanged");
+    }
+
+    #[test]
+    fn test_num_weakly_connected_components() {
+        assert_eq!(Manifold::new().num_weakly_connected_components(), 0);
+
+        let mut manifold = Manifold::new();
+        for id in ["A", "B", "C", "X", "Y"] {
+            manifold.add_node(ManifoldNode::new(id, ""));
+        }
+        manifold.add_edge("A", "B").unwrap();
+        manifold.add_edge("C", "B").unwrap();
+        manifold.add_edge("Y", "X").unwrap();
+        assert_eq!(manifold.num_weakly_connected_components(), 2);
+        assert_eq!(
+            manifold.num_weakly_connected_components(),
+            manifold.weakly_connected_components().len()
+        );
+    }
+
+    #[test]
+    fn test_contract_edge_with_merges_data() {
+        let mut manifold = Manifold::new();
+        for (id, data) in [("in", "0"), ("a", "left"), ("b", "right"), ("out", "9")] {
+            manifold.add_node(ManifoldNode::new(id, data));
+        }
+        manifold.add_edge("in", "b").unwrap();
+        manifold.add_edge("a", "b").unwrap();
+        manifold.add_edge("b", "out").unwrap();
+        manifold.set_edge_weight("b", "out", 2.0).unwrap();
+
+        let mut kept = manifold.clone();
+        kept.contract_edge("a", "b").unwrap();
+        assert_eq!(kept.nodes["a"].data, "left");
+
+        manifold.contract_edge_with("a", "b", |a, b| format!("{}+{}", a, b)).unwrap();
+        assert!(!manifold.nodes.contains_key("b"));
+        assert_eq!(manifold.nodes["a"].data, "left+right");
+        assert_eq!(manifold.edges["in"], [Edge::new("a")]);
+        assert_eq!(manifold.edges["a"], [Edge::weighted("out", 2.0)]);
+        *kept.get_node_mut("a").unwrap().data = "left+right".into();
+        assert_eq!(manifold, kept, "Only the merged data differs from contract_edge");
+
+        assert!(manifold.contract_edge("out", "a").is_err());
+    }
+
+    #[test]
+    fn test_degrees_roots_and_leaves_on_diamond() {
+        let mut manifold = Manifold::new();
+        for id in ["top", "left", "right", "bottom"] {
+            manifold.add_node(ManifoldNode::new(id, ""));
+        }
+        manifold.add_edge("top", "left").unwrap();
+        manifold.add_edge("top", "right").unwrap();
+        manifold.add_edge("left", "bottom").unwrap();
+        manifold.add_edge("right", "bottom").unwrap();
+
+        assert_eq!(manifold.out_degree("top"), Some(2));
+        assert_eq!(manifold.in_degree("top"), Some(0));
+        assert_eq!(manifold.out_degree("left"), Some(1));
+        assert_eq!(manifold.in_degree("left"), Some(1));
+        assert_eq!(manifold.in_degree("bottom"), Some(2));
+        assert_eq!(manifold.out_degree("bottom"), Some(0));
+        assert_eq!(manifold.in_degree("missing"), None);
+        assert_eq!(manifold.out_degree("missing"), None);
+
+        assert_eq!(manifold.roots(), ["top"]);
+        assert_eq!(manifold.leaves(), ["bottom"]);
+    }
+
+    #[test]
+    fn test_bfs_and_dfs_traversal() {
+        let ids = |nodes: Vec<&ManifoldNode>| nodes.into_iter().map(|n| n.id.clone()).collect::<Vec<_>>();
+
+        let mut tree = Manifold::new();
+        for id in ["root", "a", "b", "a1", "a2", "b1"] {
+            tree.add_node(ManifoldNode::new(id, ""));
+        }
+        for (from, to) in [("root", "a"), ("root", "b"), ("a", "a1"), ("a", "a2"), ("b", "b1")] {
+            tree.add_edge(from, to).unwrap();
+        }
+        assert_eq!(ids(tree.bfs("root").collect()), ["root", "a", "b", "a1", "a2", "b1"]);
+        assert_eq!(ids(tree.dfs("root").collect()), ["root", "a", "a1", "a2", "b", "b1"]);
+        assert_eq!(tree.bfs("missing").count(), 0);
+        assert_eq!(tree.dfs("missing").count(), 0);
+
+        let mut diamond = Manifold::new();
+        for id in ["top", "left", "right", "merge"] {
+            diamond.add_node(ManifoldNode::new(id, ""));
+        }
+        for (from, to) in [("top", "left"), ("top", "right"), ("left", "merge"), ("right", "merge"), ("merge", "top")] {
+            diamond.add_edge(from, to).unwrap();
+        }
+        assert_eq!(ids(diamond.dfs("top").collect()), ["top", "left", "merge", "right"]);
+        assert_eq!(ids(diamond.bfs("top").collect()), ["top", "left", "right", "merge"]);
+    }
+
+    #[test]
+    fn test_articulation_points() {
+        // Two triangles joined through "bridge": a1-a2-a3 - bridge - b1-b2-b3.
+        let mut manifold = Manifold::new();
+        for id in ["a1", "a2", "a3", "bridge", "b1", "b2", "b3"] {
+            manifold.add_node(ManifoldNode::new(id, ""));
+        }
+        for (from, to) in [
+            ("a1", "a2"), ("a2", "a3"), ("a3", "a1"),
+            ("a3", "bridge"), ("bridge", "b1"),
+            ("b1", "b2"), ("b2", "b3"), ("b3", "b1"),
+        ] {
+            manifold.add_edge(from, to).unwrap();
+        }
+        assert_eq!(manifold.articulation_points(), ["a3", "b1", "bridge"]);
+
+        // Closing a second path around the bridge removes every cut vertex.
+        manifold.add_edge("a1", "b2").unwrap();
+        assert!(manifold.articulation_points().is_empty());
+        assert!(Manifold::new().articulation_points().is_empty());
+    }
+
+    #[test]
+    fn test_manifold_node_value_parsing_policy() {
+        assert_eq!(parse_numeric(" 42 "), Some(42.0));
+        assert_eq!(parse_numeric("abc"), None);
+        assert_eq!(parse_numeric("1,5"), None);
+        assert_eq!(parse_numeric("NaN"), None);
+
+        let mut manifold = Manifold::new();
+        manifold.add_node(ManifoldNode::new("a", "7"));
+        manifold.add_node(ManifoldNode::new("b", " -0.5"));
+        manifold.add_node(ManifoldNode::new("c", "seven"));
+        assert_eq!(manifold.node_value("a").unwrap(), Some(7.0));
+        assert_eq!(manifold.node_value("b").unwrap(), Some(-0.5));
+        assert_eq!(manifold.node_value("c").unwrap(), None);
+        assert!(manifold.node_value("missing").is_err());
+        assert_eq!(manifold.nodes["a"].value(), Some(7.0));
+    }
+
+    #[test]
+    fn test_manifold_merge_and_policies() {
+        let mut left = Manifold::new();
+        left.add_node(ManifoldNode::new("a", "left"));
+        left.add_node(ManifoldNode::new("b", "left"));
+        left.add_edge("a", "b").unwrap();
+
+        let mut right = Manifold::new();
+        right.add_node(ManifoldNode::new("c", "right"));
+        right.add_node(ManifoldNode::new("d", "right"));
+        right.add_edge("c", "d").unwrap();
+
+        let mut disjoint = left.clone();
+        disjoint.merge(&right).unwrap();
+        assert_eq!(disjoint.nodes.len(), 4);
+        assert_eq!(disjoint.edges["a"], [Edge::new("b")]);
+        assert_eq!(disjoint.edges["c"], [Edge::new("d")]);
+
+        let mut overlap = Manifold::new();
+        overlap.add_node(ManifoldNode::new("b", "right"));
+        overlap.add_node(ManifoldNode::new("e", "right"));
+        overlap.add_edge("b", "e").unwrap();
+
+        let mut strict = left.clone();
+        assert!(strict.merge(&overlap).is_err());
+        assert_eq!(strict, left);
+
+        let mut kept = left.clone();
+        kept.merge_with(&overlap, MergePolicy::KeepExisting).unwrap();
+        assert_eq!(kept.nodes["b"].data, "left");
+        assert_eq!(kept.edges["b"], [Edge::new("e")]);
+
+        let mut overwritten = left.clone();
+        overwritten.merge_with(&overlap, MergePolicy::Overwrite).unwrap();
+        assert_eq!(overwritten.nodes["b"].data, "right");
+        assert_eq!(overwritten.nodes.len(), 3);
+
+        // Edges already present aren't duplicated, so a self-merge is a no-op.
+        for policy in [MergePolicy::KeepExisting, MergePolicy::Overwrite] {
+            let mut merged = disjoint.clone();
+            merged.merge_with(&disjoint, policy).unwrap();
+            assert_eq!(merged, disjoint);
+            assert_eq!(merged.edges.values().map(Vec::len).sum::<usize>(), 2);
+        }
+    }
+
+    #[test]
+    fn test_manifold_to_json_with_data_encodings() {
+        let big = "x".repeat(3000);
+        let mut manifold = Manifold::new();
+        manifold.add_node(ManifoldNode::new("big", &big));
+        manifold.add_node(ManifoldNode::new("small", "Man"));
+        manifold.add_node(ManifoldNode::new("tiny", "Ma"));
+        manifold.add_edge("big", "small").unwrap();
+
+        let inline = manifold.to_json_with(SerializeOptions::default()).unwrap();
+        assert_eq!(inline, manifold.to_json().unwrap());
+        assert_eq!(Manifold::from_json(&inline).unwrap(), manifold);
+
+        let options = |data| SerializeOptions { data };
+        let encoded = Manifold::from_json(&manifold.to_json_with(options(DataEncoding::Base64)).unwrap()).unwrap();
+        assert_eq!(encoded.nodes["small"].data, "TWFu");
+        assert_eq!(encoded.nodes["tiny"].data, "TWE=");
+        assert_eq!(encoded.nodes["big"].data.len(), 4000);
+        assert_eq!(encoded.edges["big"], [Edge::new("small")]);
+
+        let omitted = manifold.to_json_with(options(DataEncoding::Omit)).unwrap();
+        assert!(omitted.len() < 500);
+        let structure = Manifold::from_json(&omitted).unwrap();
+        assert!(structure.nodes.values().all(|node| node.data.is_empty()));
+        assert_eq!(structure.edges, manifold.edges);
+    }
+
+    #[test]
+    fn test_manifold_align_matches_renamed_node_by_data() {
+        let mut before = Manifold::new();
+        before.add_node(ManifoldNode::new("a", "alpha"));
+        before.add_node(ManifoldNode::new("b", "beta"));
+        before.add_node(ManifoldNode::new("gone", "lost"));
+
+        let mut after = Manifold::new();
+        after.add_node(ManifoldNode::new("a", "changed"));
+        after.add_node(ManifoldNode::new("b2", "beta"));
+        after.add_node(ManifoldNode::new("new", "fresh"));
+
+        let some = |id: &str| Some(id.to_string());
+        assert_eq!(
+            before.align(&after),
+            vec![
+                (some("a"), some("a")),
+                (some("b"), some("b2")),
+                (some("gone"), None),
+                (None, some("new")),
+            ]
+        );
+
+        // Nodes with similar, not identical, data pair up after exact matches.
+        before.add_node(ManifoldNode::new("c", "gamma-1"));
+        before.add_node(ManifoldNode::new("n", "100"));
+        after.add_node(ManifoldNode::new("c2", "gamma-2"));
+        after.add_node(ManifoldNode::new("n2", "101"));
+        assert_eq!(
+            before.align(&after),
+            vec![
+                (some("a"), some("a")),
+                (some("b"), some("b2")),
+                (some("c"), some("c2")),
+                (some("gone"), None),
+                (some("n"), some("n2")),
+                (None, some("new")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_manifold_orphans_and_validate() {
+        let mut manifold = Manifold::new();
+        for id in ["a", "b", "lonely"] {
+            manifold.add_node(ManifoldNode::new(id, ""));
+        }
+        manifold.add_edge("a", "b").unwrap();
+        assert_eq!(manifold.orphans(), vec!["lonely".to_string()]);
+        assert!(manifold.validate().is_ok());
+
+        manifold.edges.entry("b".into()).or_default().push(Edge::new("ghost"));
+        manifold.edges.insert("phantom".into(), vec![Edge::new("a")]);
+        match manifold.validate() {
+            Err(UorError::General(message)) => assert_eq!(
+                message,
+                "Manifold is invalid: edge b->ghost points at missing node ghost; \
+                 edge phantom->a points at missing node phantom"
+            ),
+            other => panic!("Expected both problems to be reported, got {:?}", other),
+        }
+        assert_eq!(manifold.orphans(), vec!["lonely".to_string()]);
+    }
+
     // 3. Concurrency Tests
     // ---------------------
     #[test]
@@ -76,14 +1233,90 @@ mod tests {
         let mut scheduler = RoundRobinScheduler::default();
         let manifold = Manifold::new();
         let result = scheduler.schedule(&manifold);
-        assert!(result.is_ok(), "RoundRobinScheduler scheduling should succeed in this stub test");
+        assert!(result.is_ok(), "RoundRobinScheduler scheduling should succeed on an empty manifold");
+    }
+
+    #[test]
+    fn test_round_robin_assigns_each_node_once() {
+        let mut manifold = Manifold::new();
+        for i in 0..10 {
+            manifold.add_node(ManifoldNode::new(&format!("n{}", i), ""));
+        }
+
+        let mut scheduler = RoundRobinScheduler::new(3);
+        scheduler.schedule(&manifold).unwrap();
+        assert_eq!(scheduler.node_counts(), [4, 3, 3]);
+
+        let mut assigned: Vec<&String> = scheduler.buckets().iter().flatten().collect();
+        assigned.sort();
+        let mut expected: Vec<&String> = manifold.nodes.keys().collect();
+        expected.sort();
+        assert_eq!(assigned, expected);
+
+        assert!(RoundRobinScheduler::default().workers >= 1);
+        assert!(matches!(
+            RoundRobinScheduler::new(0).schedule(&manifold),
+            Err(UorError::ConcurrencyError(_))
+        ));
+    }
+
+    #[test]
+    fn test_round_robin_schedule_report() {
+        let mut manifold = Manifold::new();
+        for i in 0..6 {
+            manifold.add_node(ManifoldNode::new(&format!("n{}", i), ""));
+        }
+
+        let report = RoundRobinScheduler::new(3).schedule(&manifold).unwrap();
+        assert_eq!(report.task_count, 6);
+        assert_eq!(report.worker_count, 3);
+        assert_eq!(report.assignments.len(), 6);
+        let mut per_worker = [0; 3];
+        for &worker in report.assignments.values() {
+            per_worker[worker] += 1;
+        }
+        assert_eq!(per_worker, [2, 2, 2]);
+    }
+
+    #[test]
+    fn test_round_robin_runs_task_on_every_node() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::{Arc, Mutex};
+
+        let mut manifold = Manifold::new();
+        for i in 0..7 {
+            manifold.add_node(ManifoldNode::new(&format!("n{}", i), &i.to_string()));
+        }
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&seen);
+        let mut scheduler = RoundRobinScheduler::new(3).with_task(move |node| log.lock().unwrap().push(node.id.clone()));
+        scheduler.schedule(&manifold).unwrap();
+        let mut seen = seen.lock().unwrap().clone();
+        seen.sort();
+        assert_eq!(seen, ["n0", "n1", "n2", "n3", "n4", "n5", "n6"]);
+
+        // A panicking task fails the call instead of the caller.
+        let done = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&done);
+        let mut scheduler = RoundRobinScheduler::new(2).with_task(move |node| {
+            if node.id == "n3" {
+                panic!("task failed on {}", node.id);
+            }
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        match scheduler.schedule(&manifold) {
+            Err(UorError::ConcurrencyError(msg)) => assert_eq!(msg, "Scheduler worker 1 panicked"),
+            other => panic!("Expected a ConcurrencyError, got {:?}", other),
+        }
+        assert!(done.load(Ordering::SeqCst) >= 4, "The other worker's bucket still ran");
     }
 
     // 4. HPC Operator Tests
     // ----------------------
     #[test]
     fn test_example_operator() {
-        let operator = ExampleOperator::default();
+        let operator = ExampleOperator;
         let manifold_in = Manifold::new();
         let result = operator.apply(&manifold_in);
         assert!(result.is_ok(), "Applying ExampleOperator should succeed");
@@ -93,18 +1326,572 @@ mod tests {
         assert_eq!(manifold_in.edges.len(), manifold_out.edges.len());
     }
 
-    // 5. Foundation Model Tests
-    // --------------------------
-    #[test]
-    fn test_null_foundation_model() {
-        let mut model = NullFoundationModel::default();
-        let manifold_in = Manifold::new();
-        let result = model.process_manifold(&manifold_in);
-        assert!(result.is_ok());
-        let manifold_out = result.unwrap();
-        // Null model returns the input unchanged
-        assert_eq!(manifold_in.nodes.len(), manifold_out.nodes.len());
-        assert_eq!(manifold_in.edges.len(), manifold_out.edges.len());
+    #[test]
+    fn test_named_composite_run_until() {
+        // Each stage tags the manifold with a node named after itself.
+        struct TagOperator(&'static str);
+        impl HpcOperator for TagOperator {
+            fn apply(&self, manifold: &Manifold) -> UorResult<Manifold> {
+                let mut out = manifold.clone();
+                out.add_node(ManifoldNode::new(self.0, ""));
+                Ok(out)
+            }
+        }
+
+        let pipeline = NamedCompositeOperator::new()
+            .with_stage("first", Box::new(TagOperator("first")))
+            .with_stage("second", Box::new(TagOperator("second")))
+            .with_stage("third", Box::new(TagOperator("third")));
+
+        let partial = pipeline.run_until(&Manifold::new(), "first").unwrap();
+        assert_eq!(partial.nodes.len(), 1);
+        assert!(partial.nodes.contains_key("first"));
+
+        let full = pipeline.apply(&Manifold::new()).unwrap();
+        assert_eq!(full.nodes.len(), 3);
+
+        assert!(pipeline.run_until(&Manifold::new(), "missing").is_err());
+
+        // A failing stage is named in the error.
+        struct FailOperator;
+        impl HpcOperator for FailOperator {
+            fn apply(&self, _manifold: &Manifold) -> UorResult<Manifold> {
+                Err(UorError::General("boom".into()))
+            }
+        }
+        let failing = NamedCompositeOperator::new()
+            .with_stage("first", Box::new(TagOperator("first")))
+            .with_stage("broken", Box::new(FailOperator));
+        assert!(failing.run_until(&Manifold::new(), "first").is_ok());
+        let error = failing.run_until(&Manifold::new(), "broken").unwrap_err();
+        assert!(matches!(error, UorError::General(msg) if msg == "Operator stage broken failed: boom"));
+    }
+
+    #[test]
+    fn test_kmeans_operator() {
+        let mut manifold = Manifold::new();
+        for (id, data) in [("a", "1.0"), ("b", "1.5"), ("c", "2.0"), ("d", "100"), ("e", "101"), ("label", "text")] {
+            manifold.add_node(ManifoldNode::new(id, data));
+        }
+
+        for seed in [0, 7, 42] {
+            let clustered = KMeansOperator::new(2, seed).apply(&manifold).unwrap();
+            let cluster = |id: &str| clustered.nodes[id].metadata.get("cluster").cloned();
+            for low in ["a", "b", "c"] {
+                assert_eq!(cluster(low).as_deref(), Some("0"), "seed {}", seed);
+            }
+            for high in ["d", "e"] {
+                assert_eq!(cluster(high).as_deref(), Some("1"), "seed {}", seed);
+            }
+            assert_eq!(cluster("label"), None, "Non-numeric nodes are not clustered");
+        }
+
+        // k is capped at the number of points: every node gets its own cluster.
+        let capped = KMeansOperator::new(50, 1).apply(&manifold).unwrap();
+        assert_eq!(capped.nodes["e"].metadata["cluster"], "4");
+
+        assert!(KMeansOperator::new(0, 1).apply(&manifold).is_err());
+    }
+
+    #[test]
+    fn test_cycle_breaker_operator() {
+        let mut manifold = Manifold::new();
+        for id in ["A", "B", "C", "D"] {
+            manifold.add_node(ManifoldNode::new(id, ""));
+        }
+        for (from, to) in [("A", "B"), ("B", "C"), ("C", "A"), ("C", "D")] {
+            manifold.add_edge(from, to).unwrap();
+        }
+        assert_eq!(manifold.find_cycle(), Some(vec!["A".into(), "B".into(), "C".into()]));
+
+        let broken = CycleBreakerOperator.apply(&manifold).unwrap();
+        assert!(broken.is_acyclic());
+        assert_eq!(broken.find_cycle(), None);
+        assert_eq!(broken.metadata["removed_edges"], "C->A");
+        assert_eq!(broken.edges["C"], [Edge::new("D")]);
+        assert_eq!(broken.edges["A"], [Edge::new("B")]);
+
+        // Already-acyclic input passes through untouched.
+        assert_eq!(CycleBreakerOperator.apply(&broken).unwrap().metadata, broken.metadata);
+    }
+
+    #[test]
+    fn test_relabel_operator() {
+        use std::collections::HashMap;
+
+        let mut manifold = Manifold::new();
+        for id in ["A", "B", "C"] {
+            manifold.add_node(ManifoldNode::new(id, id));
+        }
+        manifold.add_edge("A", "B").unwrap();
+        manifold.add_edge("B", "C").unwrap();
+
+        let mapping = HashMap::from([("A".to_string(), "ns:A".to_string()), ("B".to_string(), "ns:B".to_string())]);
+        let relabeled = RelabelOperator::new(mapping).apply(&manifold).unwrap();
+        let mut ids: Vec<&str> = relabeled.nodes.keys().map(String::as_str).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["C", "ns:A", "ns:B"]);
+        assert_eq!(relabeled.nodes["ns:A"].id, "ns:A");
+        assert_eq!(relabeled.nodes["ns:A"].data, "A");
+        assert_eq!(relabeled.edges["ns:A"], [Edge::new("ns:B")]);
+        assert_eq!(relabeled.edges["ns:B"], [Edge::new("C")]);
+
+        // Mapping A onto the unmapped C would merge two nodes.
+        let collision = HashMap::from([("A".to_string(), "C".to_string())]);
+        let result = RelabelOperator::new(collision).apply(&manifold);
+        assert!(matches!(result, Err(UorError::General(msg)) if msg.contains("A and C")));
+    }
+
+    #[test]
+    fn test_component_label_operator() {
+        let mut manifold = Manifold::new();
+        for id in ["A", "B", "C", "X", "Y"] {
+            manifold.add_node(ManifoldNode::new(id, ""));
+        }
+        // Edge direction doesn't matter: C only has an incoming edge.
+        manifold.add_edge("A", "B").unwrap();
+        manifold.add_edge("B", "C").unwrap();
+        manifold.add_edge("Y", "X").unwrap();
+
+        let labeled = ComponentLabelOperator.apply(&manifold).unwrap();
+        let label = |id: &str| labeled.nodes[id].metadata["component"].clone();
+        for id in ["A", "B", "C"] {
+            assert_eq!(label(id), "0");
+        }
+        for id in ["X", "Y"] {
+            assert_eq!(label(id), "1");
+        }
+    }
+
+    #[test]
+    fn test_observer_operator_sees_result() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let mut manifold = Manifold::new();
+        manifold.add_node(ManifoldNode::new("A", ""));
+        manifold.add_node(ManifoldNode::new("B", ""));
+
+        let seen: Rc<RefCell<Option<Manifold>>> = Rc::default();
+        let sink = Rc::clone(&seen);
+        let tap = ObserverOperator::new(
+            Box::new(ComponentLabelOperator),
+            Box::new(move |m: &Manifold| *sink.borrow_mut() = Some(m.clone())),
+        );
+        let pipeline = NamedCompositeOperator::new()
+            .with_stage("label", Box::new(tap))
+            .with_stage("example", Box::new(ExampleOperator));
+        let output = pipeline.apply(&manifold).unwrap();
+
+        let seen = seen.borrow().clone().expect("observer was called");
+        assert!(seen.nodes["A"].metadata.contains_key("component"));
+        assert_eq!(seen, output);
+    }
+
+    #[test]
+    fn test_depth_operator_on_diamond() {
+        let mut manifold = Manifold::new();
+        for id in ["top", "short", "mid", "long", "merge"] {
+            manifold.add_node(ManifoldNode::new(id, ""));
+        }
+        // top -> short -> merge and top -> mid -> long -> merge.
+        for (from, to) in [("top", "short"), ("short", "merge"), ("top", "mid"), ("mid", "long"), ("long", "merge")] {
+            manifold.add_edge(from, to).unwrap();
+        }
+
+        let annotated = DepthOperator.apply(&manifold).unwrap();
+        let depth = |id: &str| annotated.nodes[id].metadata["depth"].clone();
+        assert_eq!(depth("top"), "0");
+        assert_eq!(depth("short"), "1");
+        assert_eq!(depth("long"), "2");
+        assert_eq!(depth("merge"), "3");
+
+        manifold.add_edge("merge", "top").unwrap();
+        assert!(DepthOperator.apply(&manifold).is_err());
+    }
+
+    #[test]
+    fn test_collapse_parallel_edges() {
+        let mut manifold = Manifold::new();
+        for id in ["A", "B", "C"] {
+            manifold.add_node(ManifoldNode::new(id, ""));
+        }
+        for weight in [1.0, 2.0, 3.0] {
+            manifold.add_weighted_edge("A", "B", weight).unwrap();
+        }
+        manifold.add_weighted_edge("A", "C", 5.0).unwrap();
+
+        let mut collapsed = CollapseParallelOperator.apply(&manifold).unwrap();
+        assert_eq!(collapsed.edges["A"], [Edge::weighted("B", 6.0), Edge::weighted("C", 5.0)]);
+        assert!(collapsed.simple);
+        collapsed.add_edge("A", "B").unwrap();
+        assert_eq!(collapsed.edges["A"].len(), 2);
+    }
+
+    #[test]
+    fn test_degree_feature_operator() {
+        let mut manifold = Manifold::new();
+        for id in ["hub", "a", "b", "c"] {
+            manifold.add_node(ManifoldNode::new(id, ""));
+        }
+        for (from, to) in [("hub", "a"), ("hub", "b"), ("hub", "c"), ("a", "b"), ("c", "hub")] {
+            manifold.add_edge(from, to).unwrap();
+        }
+
+        let featured = DegreeFeatureOperator.apply(&manifold).unwrap();
+        let degrees = |id: &str| {
+            let metadata = &featured.nodes[id].metadata;
+            (metadata["in_degree"].clone(), metadata["out_degree"].clone())
+        };
+        assert_eq!(degrees("hub"), ("1".into(), "3".into()));
+        assert_eq!(degrees("a"), ("1".into(), "1".into()));
+        assert_eq!(degrees("b"), ("2".into(), "0".into()));
+        assert_eq!(degrees("c"), ("1".into(), "1".into()));
+    }
+
+    #[test]
+    fn test_composite_short_circuits_empty_manifold() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let runs = Rc::new(Cell::new(0));
+        let counter = Rc::clone(&runs);
+        let counted = ObserverOperator::new(
+            Box::new(ExampleOperator),
+            Box::new(move |_: &Manifold| counter.set(counter.get() + 1)),
+        );
+        let mut pipeline = NamedCompositeOperator::new().with_stage("counted", Box::new(counted));
+
+        pipeline.apply(&Manifold::new()).unwrap();
+        assert_eq!(runs.get(), 1);
+
+        pipeline.short_circuit_empty = true;
+        assert_eq!(pipeline.apply(&Manifold::new()).unwrap(), Manifold::new());
+        assert_eq!(pipeline.run_until(&Manifold::new(), "counted").unwrap(), Manifold::new());
+        assert_eq!(runs.get(), 1);
+
+        let mut manifold = Manifold::new();
+        manifold.add_node(ManifoldNode::new("A", ""));
+        pipeline.apply(&manifold).unwrap();
+        assert_eq!(runs.get(), 2);
+
+        let counter = Rc::clone(&runs);
+        let counted = ObserverOperator::new(
+            Box::new(ExampleOperator),
+            Box::new(move |_: &Manifold| counter.set(counter.get() + 1)),
+        );
+        let mut composite = CompositeOperator::new().then(Box::new(counted));
+        composite.apply(&Manifold::new()).unwrap();
+        assert_eq!(runs.get(), 3);
+
+        composite.short_circuit_empty = true;
+        assert_eq!(composite.apply(&Manifold::new()).unwrap(), Manifold::new());
+        assert_eq!(runs.get(), 3);
+        composite.apply(&manifold).unwrap();
+        assert_eq!(runs.get(), 4);
+    }
+
+    #[test]
+    fn test_schema_validate_operator() {
+        let chart = Chart::from_json("schema", "1.0", r#"{"required_nodes":["input","output"],"max_nodes":3}"#)
+            .unwrap();
+        let validator = SchemaValidateOperator::from_chart(&chart).unwrap();
+
+        let mut manifold = Manifold::new();
+        manifold.add_node(ManifoldNode::new("input", "1"));
+        manifold.add_node(ManifoldNode::new("hidden", "2"));
+        match validator.apply(&manifold) {
+            Err(UorError::General(message)) => assert!(message.contains("output"), "Unexpected message: {}", message),
+            other => panic!("Expected a schema violation, got {:?}", other),
+        }
+
+        manifold.add_node(ManifoldNode::new("output", "3"));
+        assert_eq!(validator.apply(&manifold).unwrap(), manifold);
+
+        manifold.add_node(ManifoldNode::new("extra", "4"));
+        assert!(validator.apply(&manifold).is_err());
+
+        let malformed = Chart::from_json("bad", "1.0", r#"{"max_nodes":"three"}"#).unwrap();
+        assert!(SchemaValidateOperator::from_chart(&malformed).is_err());
+    }
+
+    #[test]
+    fn test_weighted_pagerank_follows_heavier_edge() {
+        let mut manifold = Manifold::new();
+        for id in ["hub", "heavy", "light"] {
+            manifold.add_node(ManifoldNode::new(id, ""));
+        }
+        manifold.add_edge("hub", "heavy").unwrap();
+        manifold.add_edge("hub", "light").unwrap();
+        manifold.set_edge_weight("hub", "heavy", 3.0).unwrap();
+
+        let rank = |output: &Manifold, id: &str| output.nodes[id].metadata["pagerank"].parse::<f64>().unwrap();
+
+        let uniform = PageRankOperator::new().apply(&manifold).unwrap();
+        assert!((rank(&uniform, "heavy") - rank(&uniform, "light")).abs() < 1e-9);
+
+        let weighted = PageRankOperator { weighted: true, ..PageRankOperator::new() }.apply(&manifold).unwrap();
+        assert!(rank(&weighted, "heavy") > rank(&weighted, "light"));
+        let total: f64 = ["hub", "heavy", "light"].iter().map(|id| rank(&weighted, id)).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+
+        // A node whose out-edges all weigh zero is treated as dangling.
+        manifold.set_edge_weight("hub", "heavy", 0.0).unwrap();
+        manifold.set_edge_weight("hub", "light", 0.0).unwrap();
+        let dangling = PageRankOperator { weighted: true, ..PageRankOperator::new() }.apply(&manifold).unwrap();
+        assert!((rank(&dangling, "heavy") - rank(&dangling, "hub")).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_composite_operator_chains_stages() {
+        let mut manifold = Manifold::new();
+        manifold.add_node(ManifoldNode::new("A", "1"));
+        manifold.add_node(ManifoldNode::new("B", "2"));
+        manifold.add_edge("A", "B").unwrap();
+
+        let composite = CompositeOperator::new()
+            .then(Box::new(ExampleOperator))
+            .then(Box::new(ExampleOperator));
+        let sequential = ExampleOperator.apply(&ExampleOperator.apply(&manifold).unwrap()).unwrap();
+        assert_eq!(composite.apply(&manifold).unwrap(), sequential);
+
+        // Stage 1 fails here, because k-means needs k >= 1.
+        let failing = CompositeOperator::new()
+            .then(Box::new(DepthOperator))
+            .then(Box::new(KMeansOperator::new(0, 1)))
+            .then(Box::new(ExampleOperator));
+        match failing.apply(&manifold) {
+            Err(UorError::General(message)) => {
+                assert!(message.starts_with("Operator stage 1 failed: "), "Unexpected message: {}", message)
+            }
+            other => panic!("Expected a stage error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_canonicalize_operator_preserves_equality() {
+        let mut manifold = Manifold::new();
+        for id in ["A", "B", "C", "D"] {
+            manifold.add_node(ManifoldNode::new(id, id));
+        }
+        manifold.add_edge("A", "C").unwrap();
+        manifold.add_edge("A", "B").unwrap();
+        manifold.add_edge("A", "B").unwrap();
+        manifold.add_edge("B", "D").unwrap();
+        manifold.set_edge_weight("A", "C", 0.5).unwrap();
+        manifold.metadata.insert("run".into(), "7".into());
+        // Leave an empty adjacency list behind.
+        manifold.edges.insert("D".into(), Vec::new());
+
+        let canonical = CanonicalizeOperator.apply(&manifold).unwrap();
+        assert_eq!(canonical, manifold);
+        assert!(!canonical.edges.contains_key("D"));
+        assert_eq!(canonical.edges["A"], [Edge::new("B"), Edge::new("B"), Edge::weighted("C", 0.5)]);
+        assert_eq!(canonical.edge_weight("A", "C"), 0.5);
+    }
+
+    #[test]
+    fn test_numeric_relabel_operator_orders_sources_first() {
+        let mut manifold = Manifold::new();
+        for id in ["sink", "mid", "source"] {
+            manifold.add_node(ManifoldNode::new(id, id));
+        }
+        manifold.add_edge("source", "mid").unwrap();
+        manifold.add_edge("mid", "sink").unwrap();
+
+        let output = NumericRelabelOperator.apply(&manifold).unwrap();
+        assert_eq!(output.nodes["0"].data, "source");
+        assert_eq!(output.nodes["1"].data, "mid");
+        assert_eq!(output.nodes["2"].data, "sink");
+        assert_eq!(output.edges["0"], [Edge::new("1")]);
+        assert_eq!(output.edges["1"], [Edge::new("2")]);
+        assert_eq!(output.metadata["relabeled"], "source->0, mid->1, sink->2");
+
+        manifold.add_edge("sink", "source").unwrap();
+        assert!(NumericRelabelOperator.apply(&manifold).is_err());
+    }
+
+    #[test]
+    fn test_max_degree_operator_keeps_heaviest_edges() {
+        let mut manifold = Manifold::new();
+        manifold.add_node(ManifoldNode::new("hub", ""));
+        for (id, weight) in [("t1", 0.5), ("t2", 3.0), ("t3", 1.0), ("t4", 2.0), ("t5", 0.1)] {
+            manifold.add_node(ManifoldNode::new(id, ""));
+            manifold.add_edge("hub", id).unwrap();
+            manifold.set_edge_weight("hub", id, weight).unwrap();
+        }
+        manifold.add_edge("t1", "t2").unwrap();
+
+        let output = MaxDegreeOperator::new(2).apply(&manifold).unwrap();
+        assert_eq!(output.edges["hub"], [Edge::weighted("t2", 3.0), Edge::weighted("t4", 2.0)]);
+        assert_eq!(output.edges["t1"], [Edge::new("t2")]);
+
+        let mut unweighted = Manifold::new();
+        for id in ["hub", "c", "a", "b"] {
+            unweighted.add_node(ManifoldNode::new(id, ""));
+        }
+        for id in ["c", "a", "b"] {
+            unweighted.add_edge("hub", id).unwrap();
+        }
+        let output = MaxDegreeOperator::new(2).apply(&unweighted).unwrap();
+        assert_eq!(output.edges["hub"], [Edge::new("a"), Edge::new("b")]);
+    }
+
+    // 5. Foundation Model Tests
+    // --------------------------
+    #[test]
+    fn test_null_foundation_model() {
+        let mut model = NullFoundationModel;
+        let manifold_in = Manifold::new();
+        let result = model.process_manifold(&manifold_in);
+        assert!(result.is_ok());
+        let manifold_out = result.unwrap();
+        // Null model returns the input unchanged
+        assert_eq!(manifold_in.nodes.len(), manifold_out.nodes.len());
+        assert_eq!(manifold_in.edges.len(), manifold_out.edges.len());
+    }
+
+    #[test]
+    fn test_time_bounded_model() {
+        use std::time::Duration;
+
+        struct SlowModel(Duration);
+        impl FoundationModel for SlowModel {
+            fn process_manifold(&mut self, input: &Manifold) -> UorResult<Manifold> {
+                std::thread::sleep(self.0);
+                Ok(input.clone())
+            }
+        }
+
+        let mut slow = TimeBoundedModel::new(
+            SlowModel(Duration::from_millis(500)),
+            Duration::from_millis(20),
+        );
+        let result = slow.process_manifold(&Manifold::new());
+        assert!(matches!(result, Err(UorError::ConcurrencyError(_))));
+
+        let mut fast = TimeBoundedModel::new(
+            SlowModel(Duration::from_millis(0)),
+            Duration::from_secs(5),
+        );
+        assert!(fast.process_manifold(&Manifold::new()).is_ok());
+    }
+
+    #[test]
+    fn test_time_bounded_model_does_not_wait_for_a_hung_call() {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        struct SeededSlowModel(Arc<AtomicU64>);
+        impl FoundationModel for SeededSlowModel {
+            fn process_manifold(&mut self, input: &Manifold) -> UorResult<Manifold> {
+                std::thread::sleep(Duration::from_millis(300));
+                Ok(input.clone())
+            }
+
+            fn set_seed(&mut self, seed: u64) {
+                self.0.store(seed, Ordering::SeqCst);
+            }
+        }
+
+        let seed = Arc::new(AtomicU64::new(0));
+        let mut model = TimeBoundedModel::new(SeededSlowModel(Arc::clone(&seed)), Duration::from_millis(20));
+        model.set_seed(1);
+        assert_eq!(seed.load(Ordering::SeqCst), 1, "A free model is reseeded at once");
+
+        assert!(model.process_manifold(&Manifold::new()).is_err());
+        // The overrunning call still holds the model; neither call blocks.
+        model.set_seed(7);
+        assert!(matches!(model.warmup(), Err(UorError::ConcurrencyError(_))));
+        assert_eq!(seed.load(Ordering::SeqCst), 1);
+
+        std::thread::sleep(Duration::from_millis(600));
+        model.warmup().unwrap();
+        assert_eq!(seed.load(Ordering::SeqCst), 7, "The kept seed is applied once the model is free");
+    }
+
+    #[test]
+    fn test_model_metrics_reported_by_describe() {
+        #[derive(Default)]
+        struct CountingModel(ModelMetrics);
+        impl FoundationModel for CountingModel {
+            fn process_manifold(&mut self, input: &Manifold) -> UorResult<Manifold> {
+                self.0.manifolds_processed += 1;
+                self.0.nodes_processed += input.nodes.len();
+                Ok(input.clone())
+            }
+
+            fn metrics(&self) -> Option<ModelMetrics> {
+                Some(self.0)
+            }
+        }
+
+        let mut stack = CognitiveStack::<Box<dyn FoundationModel>>::new_default(vec![
+            Box::new(CountingModel::default()),
+            Box::new(NullFoundationModel),
+        ]);
+        let mut manifold = Manifold::new();
+        manifold.add_node(ManifoldNode::new("A", ""));
+        manifold.add_node(ManifoldNode::new("B", ""));
+        stack.process(manifold.clone()).unwrap();
+        stack.process(manifold).unwrap();
+
+        let expected = ModelMetrics { manifolds_processed: 2, nodes_processed: 4 };
+        assert_eq!(stack.describe().model_metrics, vec![Some(expected), None]);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_async_models_in_stack() {
+        use reality_engine::uor_framework::foundation_model::AsyncFoundationModel;
+
+        struct RemoteModel;
+
+        impl AsyncFoundationModel for RemoteModel {
+            async fn process_manifold(&mut self, input: &Manifold) -> UorResult<Manifold> {
+                tokio::task::yield_now().await;
+                let mut output = input.clone();
+                output.add_node(ManifoldNode::new("remote", "reply"));
+                Ok(output)
+            }
+        }
+
+        let mut manifold = Manifold::new();
+        manifold.add_node(ManifoldNode::new("A", "1"));
+        let output = RemoteModel.process_manifold(&manifold).await.unwrap();
+        assert_eq!(output.nodes["remote"].data, "reply");
+
+        // Synchronous models bridge over unchanged.
+        let mut kernel = UorKernel { weights: vec![2.0], ..Default::default() };
+        let doubled = AsyncFoundationModel::process_manifold(&mut kernel, &manifold).await.unwrap();
+        assert_eq!(doubled.nodes["A"].data, "2");
+
+        let mut stack = CognitiveStack::new_default(vec![RemoteModel, RemoteModel]);
+        stack.process_async(manifold.clone()).await.unwrap();
+        let mut sync_stack = CognitiveStack::new_default(vec![kernel]);
+        sync_stack.process_async(manifold.clone()).await.unwrap();
+        assert_eq!(sync_stack.last_schedule_report().unwrap().task_count, 1);
+
+        // Preconditions and the node budget apply as in `process`.
+        struct Picky;
+
+        impl FoundationModel for Picky {
+            fn process_manifold(&mut self, input: &Manifold) -> UorResult<Manifold> {
+                Ok(input.clone())
+            }
+
+            fn precondition(&self, _input: &Manifold) -> UorResult<()> {
+                Err(UorError::General("never satisfied".into()))
+            }
+        }
+
+        let mut picky = CognitiveStack::new_default(vec![Picky]);
+        let error = picky.process_async(manifold.clone()).await.unwrap_err();
+        assert!(matches!(error, UorError::General(msg) if msg == "model[0] rejected its input: never satisfied"));
+        stack.node_budget = Some(NodeBudget::new(1));
+        let error = stack.process_async(manifold).await.unwrap_err();
+        assert!(matches!(error, UorError::General(msg) if msg.starts_with("model[0] produced 2 nodes")));
     }
 
     // 6. Kernel Tests
@@ -121,11 +1908,65 @@ mod tests {
         assert_eq!(manifold_in.edges.len(), manifold_out.edges.len());
     }
 
+    #[test]
+    fn test_uor_kernel_with_seed_is_reproducible() {
+        let mut a = UorKernel::with_seed(42, 3);
+        let mut b = UorKernel::with_seed(42, 3);
+        assert_eq!(a.weights.len(), 3);
+        assert_eq!(a.weights, b.weights);
+        assert!(a.weights.iter().all(|w| (-0.5..0.5).contains(w)));
+        assert_ne!(a.weights, UorKernel::with_seed(43, 3).weights);
+
+        let mut manifold = Manifold::new();
+        manifold.add_node(ManifoldNode::new("features", "1.0, 2.0, 3.0"));
+        manifold.add_node(ManifoldNode::new("label", "not numeric"));
+
+        let out_a = a.process_manifold(&manifold).unwrap();
+        let out_b = b.process_manifold(&manifold).unwrap();
+        assert_eq!(out_a.nodes["features"].data, out_b.nodes["features"].data);
+        assert_ne!(out_a.nodes["features"].data, "1.0, 2.0, 3.0");
+        assert_eq!(out_a.nodes["label"].data, "not numeric");
+    }
+
+    #[test]
+    fn test_kernel_relu_clamps_negative_outputs() {
+        let mut manifold = Manifold::new();
+        manifold.add_node(ManifoldNode::new("neg", "1,2"));
+        manifold.add_node(ManifoldNode::new("pos", "3,1"));
+
+        let mut kernel = UorKernel { weights: vec![1.0, -2.0], activation: Activation::Relu, ..Default::default() };
+        let output = kernel.process_manifold(&manifold).unwrap();
+        assert_eq!(output.nodes["neg"].data, "0");
+        assert_eq!(output.nodes["pos"].data, "1");
+
+        // The default activation keeps the linear output.
+        kernel.activation = Activation::default();
+        assert_eq!(kernel.process_manifold(&manifold).unwrap().nodes["neg"].data, "-3");
+    }
+
+    #[test]
+    fn test_kernel_process_batch_matches_single_runs() {
+        let manifolds: Vec<Manifold> = (0..4)
+            .map(|i| {
+                let mut manifold = Manifold::new();
+                manifold.add_node(ManifoldNode::new("x", &format!("{},{}", i, i + 1)));
+                manifold.add_node(ManifoldNode::new("label", "text"));
+                manifold
+            })
+            .collect();
+
+        let mut kernel = UorKernel { weights: vec![0.5, -1.0], bias: 2.0, activation: Activation::Tanh, ..Default::default() };
+        let batched = kernel.process_batch(&manifolds).unwrap();
+        let single: Vec<Manifold> = manifolds.iter().map(|m| kernel.process_manifold(m).unwrap()).collect();
+        assert_eq!(batched, single);
+        assert!(kernel.process_batch(&[]).unwrap().is_empty());
+    }
+
     // 7. Embedding Tests
     // -------------------
     #[test]
     fn test_default_quaternion_embedding() {
-        let embedding = DefaultQuaternionEmbedding::default();
+        let embedding = DefaultQuaternionEmbedding;
         let mut cortex = MemoryCortex::default();
         let manifold = Manifold::new();
         let quaternions = embedding.embed_manifold(&manifold, &mut cortex)
@@ -139,6 +1980,218 @@ mod tests {
         assert_eq!(q.z, 0.0);
     }
 
+    #[test]
+    fn test_embed_into_reuses_buffer() {
+        let embedding = DefaultQuaternionEmbedding;
+        let mut cortex = MemoryCortex::default();
+        let manifold = Manifold::new();
+
+        // Start with stale contents to make sure they are cleared.
+        let mut buffer = vec![Quaternion { w: 9.0, x: 9.0, y: 9.0, z: 9.0 }; 8];
+        let capacity = buffer.capacity();
+
+        embedding.embed_into(&manifold, &mut cortex, &mut buffer)
+            .expect("First embedding should succeed");
+        assert_eq!(buffer.len(), 1);
+        assert_eq!(buffer[0].w, 1.0);
+        assert_eq!(buffer[0].x, 0.0);
+
+        // A second embedding replaces rather than appends.
+        embedding.embed_into(&manifold, &mut cortex, &mut buffer)
+            .expect("Second embedding should succeed");
+        assert_eq!(buffer.len(), 1);
+        assert_eq!(buffer[0].w, 1.0);
+        assert_eq!(buffer.capacity(), capacity, "The allocation should be reused");
+    }
+
+    #[test]
+    fn test_embedding_distance() {
+        let identity = Quaternion { w: 1.0, x: 0.0, y: 0.0, z: 0.0 };
+        let half = std::f64::consts::FRAC_1_SQRT_2;
+        // 90° about z: (cos 45°, 0, 0, sin 45°).
+        let quarter_turn = Quaternion { w: half, x: 0.0, y: 0.0, z: half };
+
+        let a = vec![identity, identity];
+        assert!(embedding_distance(&a, &a).unwrap().abs() < 1e-12);
+
+        let rotated = vec![quarter_turn, quarter_turn];
+        let distance = embedding_distance(&a, &rotated).unwrap();
+        assert!((distance - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+
+        assert!(embedding_distance(&a, &[identity]).is_err());
+    }
+
+    #[test]
+    fn test_quaternion_relative_to() {
+        let close = |a: Quaternion, b: Quaternion| {
+            (a.w - b.w).abs() < 1e-12
+                && (a.x - b.x).abs() < 1e-12
+                && (a.y - b.y).abs() < 1e-12
+                && (a.z - b.z).abs() < 1e-12
+        };
+        let half = std::f64::consts::FRAC_1_SQRT_2;
+        let a = Quaternion { w: half, x: 0.0, y: 0.0, z: half };
+        let b = Quaternion { w: 0.5, x: 0.5, y: 0.5, z: 0.5 };
+
+        assert!(close(a.relative_to(&a), Quaternion::identity()));
+
+        let r = a.relative_to(&b);
+        assert!(close(b * r, a), "Applying the relative rotation to b recovers a");
+
+        // Inputs need not be unit length.
+        let scaled = Quaternion { w: 2.0 * a.w, x: 0.0, y: 0.0, z: 2.0 * a.z };
+        assert!(close(scaled.relative_to(&b), r));
+    }
+
+    #[test]
+    fn test_quaternion_approx_eq() {
+        let q = Quaternion { w: 0.5, x: 0.5, y: 0.5, z: 0.5 };
+        let nudged = Quaternion { w: 0.5 + 1e-10, ..q };
+        let negated = Quaternion { w: -0.5, x: -0.5, y: -0.5, z: -0.5 };
+
+        assert!(q.approx_eq(&nudged, 1e-9));
+        assert!(!q.approx_eq(&nudged, 1e-11));
+
+        // -q differs component-wise but is the same rotation.
+        assert!(!q.approx_eq(&negated, 1e-9));
+        assert!(q.approx_eq_rotation(&negated, 1e-9));
+        assert!(!q.approx_eq_rotation(&Quaternion::identity(), 1e-9));
+    }
+
+    #[test]
+    fn test_weighted_topological_embedding() {
+        let mut manifold = Manifold::new();
+        manifold.add_node(ManifoldNode::new("A", "2"));
+        manifold.add_node(ManifoldNode::new("B", "1"));
+        manifold.add_node(ManifoldNode::new("C", "label"));
+        manifold.add_edge("A", "B").unwrap();
+        manifold.add_edge("B", "C").unwrap();
+
+        let mut cortex = MemoryCortex::new_144();
+        let embedding = WeightedTopologicalEmbedding::default();
+        // Unweighted: A = 2, B = 1 + 2, C = identity + 3.
+        let unweighted = embedding.embed_manifold(&manifold, &mut cortex).unwrap();
+        assert_eq!(unweighted.iter().map(|q| q.w).collect::<Vec<_>>(), vec![2.0, 3.0, 4.0]);
+
+        manifold.set_edge_weight("A", "B", 0.5).unwrap();
+        assert!(manifold.set_edge_weight("A", "C", 0.5).is_err());
+        let weighted = embedding.embed_manifold(&manifold, &mut cortex).unwrap();
+        assert_eq!(weighted.iter().map(|q| q.w).collect::<Vec<_>>(), vec![2.0, 2.0, 3.0]);
+
+        manifold.add_edge("C", "A").unwrap();
+        assert!(embedding.embed_manifold(&manifold, &mut cortex).is_err());
+    }
+
+    #[test]
+    fn test_embedding_min_norm_rejects_degenerate_nodes() {
+        let mut manifold = Manifold::new();
+        manifold.add_node(ManifoldNode::new("A", "3"));
+        manifold.add_node(ManifoldNode::new("zero", "0"));
+        let mut cortex = MemoryCortex::new_144();
+
+        // By default the zero quaternion is normalized to the identity.
+        let lenient = WeightedTopologicalEmbedding { normalize: true, ..Default::default() };
+        let out = lenient.embed_manifold(&manifold, &mut cortex).unwrap();
+        assert_eq!(out, vec![Quaternion::identity(), Quaternion::identity()]);
+
+        let strict = WeightedTopologicalEmbedding { normalize: true, min_norm: 1e-9 };
+        let result = strict.embed_manifold(&manifold, &mut cortex);
+        assert!(matches!(result, Err(UorError::EmbeddingError(msg)) if msg.contains("zero")));
+    }
+
+    #[test]
+    fn test_quaternion_arithmetic() {
+        let i = Quaternion { w: 0.0, x: 1.0, y: 0.0, z: 0.0 };
+        let j = Quaternion { w: 0.0, x: 0.0, y: 1.0, z: 0.0 };
+        let k = Quaternion { w: 0.0, x: 0.0, y: 0.0, z: 1.0 };
+        assert_eq!(i * j, k);
+        assert_eq!(j * i, Quaternion { w: 0.0, x: 0.0, y: 0.0, z: -1.0 });
+        assert_eq!(i + j, Quaternion { w: 0.0, x: 1.0, y: 1.0, z: 0.0 });
+        assert_eq!(Quaternion::identity() * k, k);
+
+        let q = Quaternion { w: 1.0, x: 2.0, y: 3.0, z: 4.0 };
+        assert_eq!(q.dot(&q.conjugate()), 1.0 - 4.0 - 9.0 - 16.0);
+        let unit = q.normalize().unwrap();
+        assert!((unit.norm() - 1.0).abs() < 1e-12);
+        assert!(Quaternion { w: 0.0, x: 0.0, y: 0.0, z: 0.0 }.normalize().is_err());
+    }
+
+    #[test]
+    fn test_quaternion_slerp() {
+        let q = Quaternion::identity();
+        let third = std::f64::consts::FRAC_PI_3;
+        // A third of a turn about the x axis, unnormalized.
+        let other = Quaternion { w: 2.0 * (third / 2.0).cos(), x: 2.0 * (third / 2.0).sin(), y: 0.0, z: 0.0 };
+        let unit_other = other.normalize().unwrap();
+
+        assert!(q.slerp(&other, 0.0).approx_eq(&q, 1e-12));
+        assert!(q.slerp(&other, 1.0).approx_eq(&unit_other, 1e-12));
+        assert!(q.slerp(&other, 1.5).approx_eq(&unit_other, 1e-12), "t is clamped");
+
+        let mid = q.slerp(&other, 0.5);
+        assert!((mid.norm() - 1.0).abs() < 1e-12);
+        let sixth = third / 2.0;
+        assert!(mid.approx_eq(&Quaternion { w: (sixth / 2.0).cos(), x: (sixth / 2.0).sin(), y: 0.0, z: 0.0 }, 1e-12));
+
+        // The shorter arc to `-other` is taken, and parallel inputs don't blow up.
+        let negated = Quaternion { w: -unit_other.w, x: -unit_other.x, y: 0.0, z: 0.0 };
+        assert!(q.slerp(&negated, 0.5).approx_eq(&mid, 1e-12));
+        assert_eq!(q.slerp(&negated, 1.0), negated, "t = 1 ends on `other`, not `-other`");
+        assert!(q.slerp(&q, 0.5).approx_eq(&q, 1e-12));
+    }
+
+    #[test]
+    fn test_quaternion_slerp_path() {
+        let start = Quaternion::identity();
+        let half = std::f64::consts::FRAC_1_SQRT_2;
+        // A quarter turn about the z axis.
+        let end = Quaternion { w: half, x: 0.0, y: 0.0, z: half };
+
+        let path = start.slerp_path(&end, 5);
+        assert_eq!(path.len(), 5);
+        assert_eq!(path[0], start);
+        assert_eq!(path[4], end);
+        for q in &path {
+            assert!((q.norm() - 1.0).abs() < 1e-12);
+        }
+        // Evenly spaced: the midpoint is an eighth turn.
+        let eighth = std::f64::consts::PI / 8.0;
+        assert!(path[2].approx_eq(&Quaternion { w: eighth.cos(), x: 0.0, y: 0.0, z: eighth.sin() }, 1e-12));
+
+        assert_eq!(start.slerp_path(&end, 2), vec![start, end]);
+        assert_eq!(start.slerp_path(&end, 1), vec![start]);
+        assert!(start.slerp_path(&end, 0).is_empty());
+
+        // Endpoints are normalized, and a negative dot product ends the
+        // path on `-end` so the last step stays short.
+        let scaled = Quaternion { w: 2.0, x: 0.0, y: 0.0, z: 0.0 };
+        let flipped = Quaternion { w: -3.0 * half, x: 0.0, y: 0.0, z: -3.0 * half };
+        let path = scaled.slerp_path(&flipped, 5);
+        assert!(path[0].approx_eq(&start, 1e-12));
+        assert!(path[4].approx_eq(&end, 1e-12));
+        assert!(path[4].approx_eq_rotation(&flipped.normalize().unwrap(), 1e-12));
+        for pair in path.windows(2) {
+            assert!(pair[0].dot(&pair[1]) > 0.9, "Consecutive samples stay close");
+        }
+    }
+
+    #[test]
+    fn test_multivector_geometric_product_of_basis_vectors() {
+        let e1 = Multivector::vector(1.0, 0.0, 0.0);
+        let e2 = Multivector::vector(0.0, 1.0, 0.0);
+        let e3 = Multivector::vector(0.0, 0.0, 1.0);
+
+        assert_eq!(e1.geometric_product(&e2), Multivector { e12: 1.0, ..Multivector::default() });
+        assert_eq!(e2 * e1, Multivector { e12: -1.0, ..Multivector::default() });
+        assert_eq!(e1 * e1, Multivector::scalar(1.0));
+        assert_eq!(e1 * e2 * e3, Multivector { e123: 1.0, ..Multivector::default() });
+        assert_eq!(e3 * e1, Multivector { e13: -1.0, ..Multivector::default() });
+
+        let mut cortex = MemoryCortex::new_144();
+        let embedded = DefaultCliffordEmbedding.embed_manifold(&Manifold::new(), &mut cortex).unwrap();
+        assert_eq!(embedded, vec![Multivector::scalar(1.0)]);
+    }
+
     // 8. Memory Cortex Tests
     // -----------------------
     #[test]
@@ -159,12 +2212,184 @@ mod tests {
         // Real test would examine changes to `cortex.references` if implemented
     }
 
+    #[test]
+    fn test_memory_cortex_aggregates() {
+        let mut cortex = MemoryCortex::default();
+        assert_eq!(cortex.filled_count(), 0);
+        assert_eq!(cortex.sum(), 0.0);
+        assert_eq!(cortex.mean(), None);
+        assert_eq!(cortex.max(), None);
+        assert_eq!(cortex.min(), None);
+
+        cortex.references[0].data = Some(2.0);
+        cortex.references[5].data = Some(-1.0);
+        cortex.references[143].data = Some(5.0);
+
+        assert_eq!(cortex.filled_count(), 3);
+        assert_eq!(cortex.sum(), 6.0);
+        assert_eq!(cortex.mean(), Some(2.0));
+        assert_eq!(cortex.max(), Some(5.0));
+        assert_eq!(cortex.min(), Some(-1.0));
+    }
+
+    #[test]
+    fn test_memory_cortex_tags() {
+        let mut cortex = MemoryCortex::default();
+        cortex.tag_slot(3, "spectral").unwrap();
+        cortex.tag_slot(10, "spectral").unwrap();
+        cortex.tag_slot(4, "lexical").unwrap();
+
+        assert_eq!(cortex.find_by_tag("spectral"), vec![3, 10]);
+        assert_eq!(cortex.find_by_tag("lexical"), vec![4]);
+        assert!(cortex.find_by_tag("unused").is_empty());
+
+        // Re-tagging replaces the old label.
+        cortex.tag_slot(10, "lexical").unwrap();
+        assert_eq!(cortex.find_by_tag("spectral"), vec![3]);
+
+        assert!(cortex.tag_slot(144, "out of range").is_err());
+    }
+
+    #[test]
+    fn test_lru_cortex_evicts_least_recently_used() {
+        let capacity = 3;
+        let mut cortex = LruCortex::new(MemoryCortex::new_144(), capacity).unwrap();
+        for slot in 0..capacity {
+            assert_eq!(cortex.set_data(slot, slot as f64).unwrap(), None);
+        }
+
+        // Reading slot 0 makes slot 1 the least recently used.
+        assert_eq!(cortex.get(0).unwrap(), Some(0.0));
+        assert_eq!(cortex.set_data(10, 10.0).unwrap(), Some(1));
+
+        assert_eq!(cortex.get(1).unwrap(), None);
+        assert_eq!(cortex.cortex().filled_count(), capacity);
+        assert_eq!(cortex.get(10).unwrap(), Some(10.0));
+        assert!(cortex.get(144).is_err());
+    }
+
+    #[test]
+    fn test_cortex_new_with_primes() {
+        let cortex = MemoryCortex::new_with_primes(&[2, 3, 5, 7, 11]).unwrap();
+        assert_eq!(cortex.references.len(), 5);
+        assert_eq!(cortex.references[4].prime_index, 4);
+        assert_eq!(cortex.references[4].prime_value, 11);
+        assert!(cortex.references.iter().all(|r| r.data.is_none()));
+    }
+
+    #[test]
+    fn test_cortex_new_with_primes_validation() {
+        assert!(MemoryCortex::new_with_primes(&[0, 2, 3]).is_err());
+        assert!(MemoryCortex::new_with_primes(&[2, 5, 3]).is_err());
+        assert!(MemoryCortex::new_with_primes(&[2, 3, 3]).is_err());
+        assert!(MemoryCortex::new_with_primes(&[]).is_err());
+    }
+
+    #[test]
+    fn test_cortex_uses_real_primes() {
+        let cortex = MemoryCortex::new_144();
+        let primes = cortex.primes();
+        assert_eq!(primes.len(), 144);
+        assert_eq!(&primes[..6], &[2, 3, 5, 7, 11, 13]);
+        assert_eq!(primes[143], 827);
+        assert!(primes.iter().all(|&p| (2..p).take_while(|d| d * d <= p).all(|d| p % d != 0)));
+        assert_eq!(cortex.references[143].prime_index, 143);
+        assert_eq!(cortex.references[143].prime_value, 827);
+    }
+
+    #[test]
+    fn test_cortex_set_get_slots() {
+        let mut cortex = MemoryCortex::new_144();
+        assert_eq!(cortex.occupied(), 0);
+
+        cortex.set(143, 2.5).unwrap();
+        cortex.set(0, -1.0).unwrap();
+        assert_eq!(cortex.get(143), Some(2.5));
+        assert_eq!(cortex.get(1), None);
+        assert_eq!(cortex.occupied(), 2);
+
+        assert!(cortex.set(144, 1.0).is_err());
+        assert_eq!(cortex.get(144), None);
+
+        cortex.tag_slot(0, "bias").unwrap();
+        cortex.clear();
+        assert_eq!(cortex.occupied(), 0);
+        assert_eq!(cortex.get(143), None);
+        assert_eq!(cortex.find_by_tag("bias"), vec![0]);
+    }
+
+    #[test]
+    fn test_cortex_diff() {
+        let mut before = MemoryCortex::new_144();
+        before.set(3, 1.0).unwrap();
+        before.set(10, 2.0).unwrap();
+        before.set(20, 5.0).unwrap();
+
+        let mut after = before.clone();
+        after.set(10, 2.5).unwrap();
+        after.set(42, -1.0).unwrap();
+
+        assert_eq!(after.diff(&after.clone()), vec![]);
+        assert_eq!(
+            before.diff(&after),
+            vec![(10, Some(2.0), Some(2.5)), (42, None, Some(-1.0))]
+        );
+    }
+
+    #[test]
+    fn test_cortex_with_capacity_and_grow() {
+        assert!(MemoryCortex::with_capacity(0).is_err());
+        assert_eq!(MemoryCortex::with_capacity(5).unwrap().primes(), [2, 3, 5, 7, 11]);
+
+        let mut cortex = MemoryCortex::new_144();
+        for i in 0..144 {
+            cortex.set(i, i as f64).unwrap();
+        }
+        cortex.grow(56);
+        assert_eq!(cortex.references.len(), 200);
+        assert!((0..144).all(|i| cortex.get(i) == Some(i as f64)));
+        assert_eq!(cortex.references[144].prime_value, 829, "The 145th prime");
+        assert_eq!(cortex.references[144].prime_index, 144);
+        assert_eq!(cortex.get(144), None);
+        assert_eq!(cortex.primes(), MemoryCortex::with_capacity(200).unwrap().primes());
+
+        // Custom tables continue with the primes after their last value.
+        let mut custom = MemoryCortex::new_with_primes(&[101]).unwrap();
+        custom.grow(2);
+        assert_eq!(custom.primes(), [101, 103, 107]);
+
+        // Far past the sieve, primes are found by testing upward.
+        let mut large = MemoryCortex::new_with_primes(&[10_000_000_019]).unwrap();
+        large.grow(2);
+        assert_eq!(large.primes(), [10_000_000_019, 10_000_000_033, 10_000_000_061]);
+    }
+
+    #[test]
+    fn test_cortex_serde_round_trip_keeps_tags() {
+        let mut cortex = MemoryCortex::new_with_primes(&[2, 3, 5]).unwrap();
+        cortex.set(0, 1.5).unwrap();
+        cortex.tag_slot(0, "bias").unwrap();
+        cortex.tag_slot(2, "bias").unwrap();
+
+        let json = serde_json::to_string(&cortex).unwrap();
+        let restored: MemoryCortex = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.find_by_tag("bias"), vec![0, 2]);
+        assert_eq!(restored.get(0), Some(1.5));
+        assert_eq!(restored.primes(), [2, 3, 5]);
+
+        // Slots out of order, or primes new_with_primes rejects, are errors.
+        let swapped = json.replace("\"prime_index\":0", "\"prime_index\":1");
+        assert!(serde_json::from_str::<MemoryCortex>(&swapped).is_err());
+        let unsorted = json.replace("\"prime_value\":5", "\"prime_value\":2");
+        assert!(serde_json::from_str::<MemoryCortex>(&unsorted).is_err());
+    }
+
     // 9. Cognitive Stack Tests
     // -------------------------
     #[test]
     fn test_cognitive_stack_default() {
         // We'll rely on NullFoundationModel for default
-        let mut stack = CognitiveStack::<NullFoundationModel>::default();
+        let stack = CognitiveStack::<NullFoundationModel>::default();
         // By default, it contains 1 model (the default NullFoundationModel)
         assert_eq!(stack.models.len(), 1);
 
@@ -172,9 +2397,28 @@ mod tests {
         assert_eq!(stack.cortex.references.len(), 144);
     }
 
+    #[test]
+    fn test_cognitive_stack_describe() {
+        let stack = CognitiveStack::<NullFoundationModel>::default();
+        let description = stack.describe();
+        assert_eq!(description.model_count, 1);
+        assert_eq!(description.model_names.len(), 1);
+        assert!(description.model_names[0].ends_with("NullFoundationModel"));
+        assert_eq!(description.cortex_size, 144);
+        assert!(description.embedding.ends_with("DefaultQuaternionEmbedding"));
+        assert!(description.operator.ends_with("ExampleOperator"));
+        assert!(description.scheduler.ends_with("RoundRobinScheduler"));
+
+        // Boxed models report the name of the model inside the box.
+        let mixed = CognitiveStack::<Box<dyn FoundationModel>>::new_default(vec![
+            Box::new(UorKernel::default()),
+        ]);
+        assert!(mixed.describe().model_names[0].ends_with("UorKernel"));
+    }
+
     #[test]
     fn test_cognitive_stack_process_single_model() {
-        let mut stack = CognitiveStack::new_default(vec![NullFoundationModel::default()]);
+        let mut stack = CognitiveStack::new_default(vec![NullFoundationModel]);
         let manifold = Manifold::new();
         let result = stack.process(manifold);
         assert!(result.is_ok(), "Processing with a single NullFoundationModel should succeed");
@@ -189,21 +2433,450 @@ mod tests {
         assert!(result.is_ok(), "Processing with UorKernel should succeed");
     }
 
+    #[test]
+    fn test_cognitive_stack_process_multi_embedding() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        struct CountingModel(Rc<Cell<usize>>);
+        impl FoundationModel for CountingModel {
+            fn process_manifold(&mut self, input: &Manifold) -> UorResult<Manifold> {
+                self.0.set(self.0.get() + 1);
+                Ok(input.clone())
+            }
+        }
+
+        // One identity quaternion per node.
+        struct PerNodeEmbedding;
+        impl QuaternionEmbedding for PerNodeEmbedding {
+            fn embed_into(
+                &self,
+                manifold: &Manifold,
+                _cortex: &mut MemoryCortex,
+                out: &mut Vec<Quaternion>,
+            ) -> UorResult<()> {
+                out.clear();
+                out.extend(manifold.nodes.values().map(|_| Quaternion { w: 1.0, x: 0.0, y: 0.0, z: 0.0 }));
+                Ok(())
+            }
+        }
+
+        let runs = Rc::new(Cell::new(0));
+        let mut stack = CognitiveStack::new_default(vec![CountingModel(Rc::clone(&runs))]);
+        let mut manifold = Manifold::new();
+        for id in ["A", "B", "C"] {
+            manifold.add_node(ManifoldNode::new(id, ""));
+        }
+
+        let embeddings: Vec<Box<dyn QuaternionEmbedding>> =
+            vec![Box::new(DefaultQuaternionEmbedding), Box::new(PerNodeEmbedding)];
+        let views = stack.process_multi_embedding(manifold, &embeddings).unwrap();
+        assert_eq!(views.len(), 2);
+        assert_eq!(views[0].len(), 1);
+        assert_eq!(views[1].len(), 3);
+        assert_eq!(runs.get(), 1, "The model pipeline should run once for all views");
+    }
+
+    #[test]
+    fn test_cognitive_stack_process_with_control() {
+        use std::ops::ControlFlow;
+
+        // Appends a node named after the model, so each stage leaves a trace.
+        struct AppendModel(&'static str);
+        impl FoundationModel for AppendModel {
+            fn process_manifold(&mut self, input: &Manifold) -> UorResult<Manifold> {
+                let mut out = input.clone();
+                out.add_node(ManifoldNode::new(self.0, ""));
+                Ok(out)
+            }
+        }
+
+        let mut stack = CognitiveStack::new_default(vec![AppendModel("first"), AppendModel("second")]);
+        let mut stages = Vec::new();
+        let partial = stack
+            .process_with_control(Manifold::new(), |stage, _| {
+                stages.push(stage.to_string());
+                ControlFlow::Break(())
+            })
+            .unwrap();
+        assert_eq!(stages, vec!["model[0]"]);
+        assert!(partial.nodes.contains_key("first"));
+        assert!(!partial.nodes.contains_key("second"), "The second model must not run");
+
+        let mut stages = Vec::new();
+        let full = stack
+            .process_with_control(Manifold::new(), |stage, _| {
+                stages.push(stage.to_string());
+                ControlFlow::Continue(())
+            })
+            .unwrap();
+        assert_eq!(stages, vec!["model[0]", "model[1]", "scheduler", "operator"]);
+        assert_eq!(full.nodes.len(), 2);
+    }
+
     #[test]
     fn test_cognitive_stack_process_multiple_models() {
         // Stack that has both NullFoundationModel and the kernel
-        let mut stack = CognitiveStack::new_default(vec![
-            NullFoundationModel::default(),
-            UorKernel::default(),
+        let mut stack = CognitiveStack::<Box<dyn FoundationModel>>::new_default(vec![
+            Box::new(NullFoundationModel),
+            Box::new(UorKernel::default()),
         ]);
 
         // Prepare a small manifold
         let mut manifold = Manifold::new();
-        manifold.add_node(ManifoldNode { id: "N1".into(), data: "D1".into() });
-        manifold.add_node(ManifoldNode { id: "N2".into(), data: "D2".into() });
+        manifold.add_node(ManifoldNode::new("N1", "D1"));
+        manifold.add_node(ManifoldNode::new("N2", "D2"));
         manifold.add_edge("N1", "N2").unwrap();
 
         let result = stack.process(manifold);
         assert!(result.is_ok(), "Processing with multiple models should succeed");
     }
+
+    #[test]
+    fn test_caching_stack_hits_on_identical_manifold() {
+        let build = |edge_order: [(&str, &str); 2]| {
+            let mut manifold = Manifold::new();
+            for id in ["A", "B", "C"] {
+                manifold.add_node(ManifoldNode::new(id, id));
+            }
+            for (from, to) in edge_order {
+                manifold.add_edge(from, to).unwrap();
+            }
+            manifold
+        };
+        // Same structure, different adjacency order.
+        let first = build([("A", "B"), ("A", "C")]);
+        let second = build([("A", "C"), ("A", "B")]);
+        assert_eq!(first.structural_hash(), second.structural_hash());
+
+        let stack = CognitiveStack::new_default(vec![NullFoundationModel]);
+        let mut caching = CachingCognitiveStack::new(stack);
+        let a = caching.process(first).unwrap();
+        let b = caching.process(second).unwrap();
+        assert_eq!(a, b);
+        assert_eq!(caching.stats(), CacheStats { hits: 1, misses: 1 });
+
+        // A different input is a miss, and both entries stay cached.
+        caching.process(build([("A", "B"), ("B", "C")])).unwrap();
+        caching.process(build([("A", "B"), ("B", "C")])).unwrap();
+        assert_eq!(caching.stats(), CacheStats { hits: 2, misses: 2 });
+
+        // Reconfiguring the stack invalidates the cache.
+        caching.stack_mut().operator = Box::new(ComponentLabelOperator);
+        caching.process(build([("A", "B"), ("A", "C")])).unwrap();
+        assert_eq!(caching.stats().misses, 3);
+    }
+
+    #[test]
+    fn test_model_handles_survive_removal() {
+        let mut stack: CognitiveStack<UorKernel> = CognitiveStack::new_default(Vec::new());
+        let first = stack.add_model(UorKernel { weights: vec![1.0], ..Default::default() }).unwrap();
+        let second = stack.add_model(UorKernel { weights: vec![2.0], ..Default::default() }).unwrap();
+        let third = stack.add_model(UorKernel { weights: vec![3.0], ..Default::default() }).unwrap();
+
+        assert_eq!(stack.remove_model(first).unwrap().weights, vec![1.0]);
+        assert!(stack.get_model(first).is_none());
+        assert!(stack.remove_model(first).is_none());
+        assert_eq!(stack.get_model(second).unwrap().weights, vec![2.0]);
+        assert_eq!(stack.get_model(third).unwrap().weights, vec![3.0]);
+
+        while stack.models.len() < MAX_MODELS {
+            stack.add_model(UorKernel::default()).unwrap();
+        }
+        assert!(stack.add_model(UorKernel::default()).is_err());
+    }
+
+    #[test]
+    fn test_try_clone_stack_processes_independently() {
+        let mut stack = CognitiveStack::<Box<dyn FoundationModel>>::new_default(vec![
+            Box::new(UorKernel { weights: vec![2.0], ..Default::default() }),
+        ]);
+        stack.operator = Box::new(NamedCompositeOperator::new().with_stage("depth", Box::new(DepthOperator)));
+        stack.cortex.tag_slot(0, "input").unwrap();
+
+        let mut copy = stack.try_clone().unwrap();
+        assert_eq!(copy.describe(), stack.describe());
+        assert_eq!(copy.cortex.find_by_tag("input"), vec![0]);
+
+        // Changing the copy leaves the original alone.
+        copy.cortex.tag_slot(1, "copy-only").unwrap();
+        assert!(stack.cortex.find_by_tag("copy-only").is_empty());
+
+        let mut manifold = Manifold::new();
+        manifold.add_node(ManifoldNode::new("A", "3"));
+        let from_copy = copy.process_with_control(manifold.clone(), |_, _| std::ops::ControlFlow::Continue(())).unwrap();
+        let from_original = stack.process_with_control(manifold, |_, _| std::ops::ControlFlow::Continue(())).unwrap();
+        assert_eq!(from_copy, from_original);
+        assert_eq!(from_copy.nodes["A"].data, "6");
+
+        // Components without clone support make the copy fail.
+        stack.operator = Box::new(ObserverOperator::new(Box::new(ExampleOperator), Box::new(|_: &Manifold| {})));
+        assert!(stack.try_clone().is_err());
+    }
+
+    #[test]
+    fn test_process_stream_is_lazy() {
+        use std::cell::Cell;
+
+        let pulled = Cell::new(0);
+        let inputs = ["1", "2", "3"].into_iter().map(|data| {
+            pulled.set(pulled.get() + 1);
+            let mut manifold = Manifold::new();
+            manifold.add_node(ManifoldNode::new("A", data));
+            manifold
+        });
+
+        let mut stack = CognitiveStack::new_default(vec![NullFoundationModel]);
+        stack.embedding = Box::new(WeightedTopologicalEmbedding::default());
+        let mut results = stack.process_stream(inputs);
+        assert_eq!(pulled.get(), 0);
+
+        let first = results.next().unwrap().unwrap();
+        assert_eq!(pulled.get(), 1);
+        assert_eq!(first[0].w, 1.0);
+        let rest: Vec<f64> = results.map(|r| r.unwrap()[0].w).collect();
+        assert_eq!(rest, vec![2.0, 3.0]);
+        assert_eq!(pulled.get(), 3);
+    }
+
+    #[test]
+    fn test_stack_seed_makes_runs_reproducible() {
+        let mut manifold = Manifold::new();
+        manifold.add_node(ManifoldNode::new("a", "1,2"));
+        manifold.add_node(ManifoldNode::new("b", "3,-1"));
+        manifold.add_node(ManifoldNode::new("c", "-2,4"));
+        manifold.add_edge("a", "b").unwrap();
+        manifold.add_edge("b", "c").unwrap();
+
+        // The embedding reads the kernel's output, so it tracks the weights.
+        let stack_with = |kernel_seed: u64, kmeans_seed: u64| {
+            let mut stack = CognitiveStack::new_default(vec![UorKernel::with_seed(kernel_seed, 2)]);
+            stack.operator = Box::new(KMeansOperator::new(2, kmeans_seed));
+            stack.embedding = Box::new(WeightedTopologicalEmbedding::default());
+            stack
+        };
+        let mut first = stack_with(1, 9);
+        let mut second = stack_with(2, 5);
+        assert_ne!(first.models[0].weights, second.models[0].weights);
+        let before = (
+            first.process_traced(manifold.clone()).unwrap(),
+            second.process_traced(manifold.clone()).unwrap(),
+        );
+        assert_ne!(before.0.embedding, before.1.embedding);
+
+        first.set_seed(42);
+        second.set_seed(42);
+        assert_eq!(first.seed(), Some(42));
+        assert_eq!(first.models[0].weights, second.models[0].weights);
+        let (a, b) = (
+            first.process_traced(manifold.clone()).unwrap(),
+            second.process_traced(manifold.clone()).unwrap(),
+        );
+        assert_eq!(a.embedding, b.embedding);
+        let clusters = |trace: &ProcessTrace| {
+            let mut clusters: Vec<_> = trace
+                .operator_output
+                .nodes
+                .values()
+                .map(|node| (node.id.clone(), node.metadata.get("cluster").cloned()))
+                .collect();
+            clusters.sort();
+            clusters
+        };
+        assert!(clusters(&a).iter().all(|(_, cluster)| cluster.is_some()));
+        assert_eq!(clusters(&a), clusters(&b));
+
+        // Models added later pick up the stack's seed; hand-set weights are kept.
+        first.add_model(UorKernel::with_seed(7, 2)).unwrap();
+        assert_eq!(first.models[1].weights, first.models[0].weights);
+        first.add_model(UorKernel { weights: vec![0.5, 0.5], ..Default::default() }).unwrap();
+        assert_eq!(first.models[2].weights, vec![0.5, 0.5]);
+    }
+
+    #[test]
+    fn test_process_traced_records_each_model() {
+        let mut manifold = Manifold::new();
+        manifold.add_node(ManifoldNode::new("x", "1"));
+
+        let mut stack = CognitiveStack::new_default(vec![
+            UorKernel { weights: vec![2.0], ..Default::default() },
+            UorKernel { weights: vec![3.0], ..Default::default() },
+        ]);
+        stack.operator = Box::new(DepthOperator);
+        let trace = stack.process_traced(manifold).unwrap();
+
+        assert_eq!(trace.model_outputs.len(), 2);
+        assert_eq!(trace.model_outputs[0].nodes["x"].data, "2");
+        assert_eq!(trace.model_outputs[1].nodes["x"].data, "6");
+        assert_eq!(trace.operator_output.nodes["x"].metadata["depth"], "0");
+        assert_eq!(trace.embedding.len(), 1);
+    }
+
+    #[test]
+    fn test_model_precondition_rejects_cyclic_input() {
+        struct AcyclicOnly;
+
+        impl FoundationModel for AcyclicOnly {
+            fn process_manifold(&mut self, input: &Manifold) -> UorResult<Manifold> {
+                Ok(input.clone())
+            }
+
+            fn precondition(&self, input: &Manifold) -> UorResult<()> {
+                match input.find_cycle() {
+                    Some(_) => Err(UorError::General("requires acyclic input".into())),
+                    None => Ok(()),
+                }
+            }
+        }
+
+        let mut manifold = Manifold::new();
+        manifold.add_node(ManifoldNode::new("A", ""));
+        manifold.add_node(ManifoldNode::new("B", ""));
+        manifold.add_edge("A", "B").unwrap();
+
+        let mut stack = CognitiveStack::new_default(vec![AcyclicOnly]);
+        stack.process(manifold.clone()).unwrap();
+
+        manifold.add_edge("B", "A").unwrap();
+        match stack.process(manifold) {
+            Err(UorError::General(message)) => {
+                assert_eq!(message, "model[0] rejected its input: requires acyclic input")
+            }
+            other => panic!("Expected the precondition to fail, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_node_budget_stops_runaway_model() {
+        /// Doubles the node count on every call.
+        struct Exploder;
+
+        impl FoundationModel for Exploder {
+            fn process_manifold(&mut self, input: &Manifold) -> UorResult<Manifold> {
+                let mut output = input.clone();
+                for i in input.nodes.len()..2 * input.nodes.len() {
+                    output.add_node(ManifoldNode::new(&format!("n{}", i), ""));
+                }
+                Ok(output)
+            }
+        }
+
+        let mut manifold = Manifold::new();
+        manifold.add_node(ManifoldNode::new("n0", ""));
+
+        let mut stack = CognitiveStack::new_default(vec![Exploder, Exploder, Exploder]);
+        stack.process(manifold.clone()).unwrap();
+
+        stack.node_budget = Some(NodeBudget::new(3));
+        match stack.process(manifold) {
+            Err(UorError::General(message)) => {
+                assert_eq!(message, "model[1] produced 4 nodes, over the budget of 3")
+            }
+            other => panic!("Expected the budget to stop the run, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_stack_builder_uses_custom_operator() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let seen = Rc::new(Cell::new(0));
+        let counter = Rc::clone(&seen);
+        let operator = ObserverOperator::new(
+            Box::new(DepthOperator),
+            Box::new(move |manifold: &Manifold| counter.set(manifold.nodes.len())),
+        );
+        let mut stack = CognitiveStackBuilder::new()
+            .add_model(NullFoundationModel)
+            .with_operator(Box::new(operator))
+            .with_scheduler(Box::new(RoundRobinScheduler::new(2)))
+            .with_cortex(MemoryCortex::with_capacity(8).unwrap())
+            .build()
+            .unwrap();
+        assert_eq!(stack.models.len(), 1);
+        assert_eq!(stack.cortex.references.len(), 8);
+        assert!(stack.describe().operator.ends_with("ObserverOperator"));
+
+        let mut manifold = Manifold::new();
+        manifold.add_node(ManifoldNode::new("A", ""));
+        manifold.add_node(ManifoldNode::new("B", ""));
+        stack.process(manifold).unwrap();
+        assert_eq!(seen.get(), 2, "The custom operator ran during process");
+
+        let too_many = (0..=MAX_MODELS).fold(CognitiveStackBuilder::new(), |b, _| b.add_model(NullFoundationModel));
+        assert!(too_many.build().is_err());
+    }
+
+    #[test]
+    fn test_stack_warmup_runs_component_warmups() {
+        #[derive(Default)]
+        struct LazyModel {
+            initialized: bool,
+        }
+
+        impl FoundationModel for LazyModel {
+            fn process_manifold(&mut self, input: &Manifold) -> UorResult<Manifold> {
+                assert!(self.initialized, "process ran before warmup");
+                Ok(input.clone())
+            }
+
+            fn warmup(&mut self) -> UorResult<()> {
+                self.initialized = true;
+                Ok(())
+            }
+        }
+
+        let mut stack = CognitiveStack::new_default(vec![LazyModel::default(), LazyModel::default()]);
+        assert!(stack.models.iter().all(|m| !m.initialized));
+        stack.warmup().unwrap();
+        assert!(stack.models.iter().all(|m| m.initialized));
+        stack.process(Manifold::new()).unwrap();
+    }
+
+    #[test]
+    fn test_counting_model_counts_stack_runs() {
+        let counter = CountingFoundationModel::new();
+        let calls = counter.clone();
+        let mut stack = CognitiveStack::new_default(vec![counter]);
+
+        let mut manifold = Manifold::new();
+        manifold.add_node(ManifoldNode::new("A", "x"));
+        for _ in 0..3 {
+            stack.process(manifold.clone()).unwrap();
+        }
+        assert_eq!(calls.calls(), 3);
+
+        let mut passthrough = CountingFoundationModel::new();
+        assert_eq!(passthrough.process_manifold(&manifold).unwrap(), manifold);
+        assert_eq!(passthrough.calls(), 1);
+        assert_eq!(passthrough.metrics().unwrap().manifolds_processed, 1);
+
+        // Boxed counters can be cloned with their stack, sharing the count.
+        let mut boxed = CognitiveStack::<Box<dyn FoundationModel>>::new_default(vec![Box::new(passthrough.clone())]);
+        let mut copy = boxed.try_clone().unwrap();
+        boxed.process(manifold.clone()).unwrap();
+        copy.process(manifold).unwrap();
+        assert_eq!(passthrough.calls(), 3);
+        assert_eq!(copy.models[0].metrics().unwrap().manifolds_processed, 3);
+    }
+
+    #[test]
+    fn test_stack_keeps_last_schedule_report() {
+        let mut stack = CognitiveStack::new_default(vec![NullFoundationModel]);
+        stack.scheduler = Box::new(RoundRobinScheduler::new(3));
+        assert!(stack.last_schedule_report().is_none());
+
+        let mut manifold = Manifold::new();
+        for i in 0..6 {
+            manifold.add_node(ManifoldNode::new(&format!("n{}", i), ""));
+        }
+        stack.process(manifold.clone()).unwrap();
+        let report = stack.last_schedule_report().unwrap();
+        assert_eq!((report.task_count, report.worker_count), (6, 3));
+
+        manifold.remove_node("n0").unwrap();
+        stack.process_traced(manifold).unwrap();
+        assert_eq!(stack.last_schedule_report().unwrap().task_count, 5);
+    }
 }